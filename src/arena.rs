@@ -0,0 +1,127 @@
+use crate::output::Output;
+use crate::traits::{Circle, Shape, Square, Triangle};
+
+//Owns every shape and hands out plain indices instead of references. Anything that needs to
+// point at a shape keeps the index, so nothing ends up borrowing from the arena while it is still
+// being added to (which is exactly the self-referential struct the borrow checker won't allow).
+pub struct ShapeArena {
+    storage: Vec<Box<dyn Shape>>,
+}
+
+impl ShapeArena {
+    pub fn new() -> Self {
+        ShapeArena { storage: Vec::new() }
+    }
+
+    //Indices are handed out in order starting at 0 and stay valid because nothing is ever
+    // removed.
+    pub fn alloc(&mut self, s: Box<dyn Shape>) -> usize {
+        self.storage.push(s);
+        self.storage.len() - 1
+    }
+
+    pub fn get(&self, id: usize) -> Option<&dyn Shape> {
+        self.storage.get(id).map(|shape| shape.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    //Any number of views can exist at the same time since they only need &self. alloc needs
+    // &mut self, so the compiler won't let the arena grow while a view is still around.
+    pub fn view(&self, min_area: isize) -> ArenaView<'_> {
+        ArenaView { shapes: self.storage.iter(), min_area }
+    }
+}
+
+impl Default for ShapeArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//Walks the arena skipping anything smaller than min_area. A shape with exactly min_area is kept.
+pub struct ArenaView<'a> {
+    shapes: std::slice::Iter<'a, Box<dyn Shape>>,
+    min_area: isize,
+}
+
+impl<'a> Iterator for ArenaView<'a> {
+    type Item = &'a dyn Shape;
+
+    fn next(&mut self) -> Option<&'a dyn Shape> {
+        let min_area = self.min_area;
+        self.shapes.find(|shape| shape.area() >= min_area).map(|shape| shape.as_ref())
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut arena = ShapeArena::new();
+    let square = arena.alloc(Box::new(Square { height: 10 }));
+    let triangle = arena.alloc(Box::new(Triangle { base: 5, height: 10 }));
+    arena.alloc(Box::new(Circle { radius: 2.0 }));
+    arena.alloc(Box::new(Square { height: 3 }));
+
+    out.line(format!(
+        "arena holds {} shapes, square is {} and triangle is {}",
+        arena.len(),
+        square,
+        triangle
+    ));
+    out.line(format!("area of shape {triangle}: {:?}", arena.get(triangle).map(|s| s.area())));
+    out.line(format!("shape 10: {}", arena.get(10).is_some()));
+
+    let big = arena.view(25);
+    let small = arena.view(0);
+    let big_areas: Vec<isize> = big.map(|shape| shape.area()).collect();
+    let small_count = small.count();
+    out.line(format!("areas of at least 25: {:?} out of {}", big_areas, small_count));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arena_of(sides: &[isize]) -> ShapeArena {
+        let mut arena = ShapeArena::new();
+        for &height in sides {
+            arena.alloc(Box::new(Square { height }));
+        }
+        arena
+    }
+
+    #[test]
+    fn alloc_hands_out_indices_in_order() {
+        let mut arena = ShapeArena::default();
+        assert!(arena.is_empty());
+        assert_eq!(arena.alloc(Box::new(Square { height: 1 })), 0);
+        assert_eq!(arena.alloc(Box::new(Triangle { base: 2, height: 2 })), 1);
+        assert_eq!(arena.len(), 2);
+        assert_eq!(arena.get(1).map(|shape| shape.area()), Some(2));
+        assert!(arena.get(2).is_none());
+    }
+
+    #[test]
+    fn view_keeps_shapes_with_exactly_min_area() {
+        let arena = arena_of(&[1, 5, 3, 6]);
+        let areas: Vec<isize> = arena.view(25).map(|shape| shape.area()).collect();
+        assert_eq!(areas, [25, 36]);
+        assert_eq!(arena.view(0).count(), 4);
+        assert_eq!(arena.view(100).count(), 0);
+    }
+
+    #[test]
+    fn views_can_be_used_side_by_side() {
+        let arena = arena_of(&[2, 4]);
+        let (mut big, mut all) = (arena.view(10), arena.view(0));
+        assert_eq!(all.next().map(|shape| shape.area()), Some(4));
+        assert_eq!(big.next().map(|shape| shape.area()), Some(16));
+        assert_eq!(all.next().map(|shape| shape.area()), Some(16));
+        assert!(big.next().is_none() && all.next().is_none());
+    }
+}
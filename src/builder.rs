@@ -0,0 +1,135 @@
+use crate::traits::{Shape, Square, Triangle};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+pub enum BuildError {
+    MissingField(&'static str),
+    NonPositive(&'static str, isize),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BuildError::MissingField(field) => write!(f, "no {field} was given"),
+            BuildError::NonPositive(field, value) => {
+                write!(f, "{field} must be positive, got {value}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+//Every method takes the builder by value and hands it back, so the calls chain one after another.
+// Setting a field twice keeps the last value. Since the builders are Clone, a half finished one
+// can be copied and finished off in different ways.
+pub struct ShapeBuilder;
+
+impl ShapeBuilder {
+    pub fn square() -> SquareBuilder {
+        SquareBuilder { height: None }
+    }
+
+    pub fn triangle() -> TriangleBuilder {
+        TriangleBuilder { base: None, height: None }
+    }
+}
+
+fn require(value: Option<isize>, field: &'static str) -> Result<isize, BuildError> {
+    match value {
+        None => Err(BuildError::MissingField(field)),
+        Some(value) if value <= 0 => Err(BuildError::NonPositive(field, value)),
+        Some(value) => Ok(value),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SquareBuilder {
+    height: Option<isize>,
+}
+
+impl SquareBuilder {
+    pub fn height(self, height: isize) -> Self {
+        SquareBuilder { height: Some(height) }
+    }
+
+    pub fn build(self) -> Result<Box<dyn Shape>, BuildError> {
+        Ok(Box::new(Square { height: require(self.height, "height")? }))
+    }
+}
+
+//The base is checked before the height, so with both missing the error names the base.
+#[derive(Debug, Clone)]
+pub struct TriangleBuilder {
+    base: Option<isize>,
+    height: Option<isize>,
+}
+
+impl TriangleBuilder {
+    pub fn base(self, base: isize) -> Self {
+        TriangleBuilder { base: Some(base), ..self }
+    }
+
+    pub fn height(self, height: isize) -> Self {
+        TriangleBuilder { height: Some(height), ..self }
+    }
+
+    pub fn build(self) -> Result<Box<dyn Shape>, BuildError> {
+        let base = require(self.base, "base")?;
+        let height = require(self.height, "height")?;
+        Ok(Box::new(Triangle { base, height }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_builder_needs_a_positive_height() {
+        assert_eq!(ShapeBuilder::square().height(4).build().map(|s| s.area()), Ok(16));
+        let err = ShapeBuilder::square().build().map(|s| s.area()).unwrap_err();
+        assert_eq!(err, BuildError::MissingField("height"));
+        let err = ShapeBuilder::square().height(0).build().map(|s| s.area()).unwrap_err();
+        assert_eq!(err, BuildError::NonPositive("height", 0));
+    }
+
+    #[test]
+    fn triangle_builder_checks_the_base_first() {
+        let built = ShapeBuilder::triangle().height(3).base(4).build();
+        assert_eq!(built.map(|s| s.area()), Ok(6));
+        let missing = ShapeBuilder::triangle().build().map(|s| s.area());
+        assert_eq!(missing, Err(BuildError::MissingField("base")));
+        let negative = ShapeBuilder::triangle().base(-1).height(-2).build().map(|s| s.area());
+        assert_eq!(negative, Err(BuildError::NonPositive("base", -1)));
+    }
+
+    #[test]
+    fn triangle_builder_names_whichever_field_is_missing() {
+        let area = |builder: TriangleBuilder| builder.build().map(|s| s.area());
+        assert_eq!(area(ShapeBuilder::triangle().base(4)), Err(BuildError::MissingField("height")));
+        assert_eq!(area(ShapeBuilder::triangle().height(4)), Err(BuildError::MissingField("base")));
+        let zero_height = ShapeBuilder::triangle().base(4).height(0);
+        assert_eq!(area(zero_height), Err(BuildError::NonPositive("height", 0)));
+    }
+
+    #[test]
+    fn setting_a_field_twice_keeps_the_last_value() {
+        let built = ShapeBuilder::square().height(-5).height(5).build();
+        assert_eq!(built.map(|s| s.area()), Ok(25));
+    }
+
+    #[test]
+    fn a_cloned_builder_can_be_finished_two_ways() {
+        let half_built = ShapeBuilder::triangle().base(6);
+        assert_eq!(half_built.clone().height(2).build().map(|s| s.area()), Ok(6));
+        assert_eq!(half_built.height(10).build().map(|s| s.area()), Ok(30));
+    }
+
+    #[test]
+    fn errors_say_which_field() {
+        assert_eq!(BuildError::MissingField("base").to_string(), "no base was given");
+        let err = BuildError::NonPositive("height", -3);
+        assert_eq!(err.to_string(), "height must be positive, got -3");
+    }
+}
@@ -0,0 +1,164 @@
+use crate::output::Output;
+use crate::spatial::Spatial;
+use crate::traits::{Shape, Square};
+use std::any::Any;
+use std::cell::Cell;
+
+//Wraps a shape and only works out its area the first time it is asked for. area takes &self,
+// so the cache can't be a plain Option field that gets assigned to. Cell gets around that by
+// letting a value be swapped in and out through a shared reference. Cell only works for Copy
+// values like Option<isize>, which is all that is needed here.
+#[derive(Clone)]
+pub struct CachedShape<S: Shape> {
+    inner: S,
+    cached_area: Cell<Option<isize>>,
+    hits: Cell<usize>,
+}
+
+impl<S: Shape> CachedShape<S> {
+    pub fn new(inner: S) -> Self {
+        CachedShape { inner, cached_area: Cell::new(None), hits: Cell::new(0) }
+    }
+
+    //The next call to area goes back to the inner shape. The hit count is left alone.
+    pub fn invalidate(&self) {
+        self.cached_area.set(None);
+    }
+
+    //How many times area was answered from the cache instead of the inner shape.
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+//Only area is cached. The other methods are cheap enough to just pass straight through.
+impl<S: Shape + Clone + 'static> Shape for CachedShape<S> {
+    fn area(&self) -> isize {
+        match self.cached_area.get() {
+            Some(area) => {
+                self.hits.set(self.hits.get() + 1);
+                area
+            }
+            None => {
+                let area = self.inner.area();
+                self.cached_area.set(Some(area));
+                area
+            }
+        }
+    }
+
+    //Overflow is the reason to call this one, so it goes straight to the inner shape rather than
+    // trusting whatever area would give back.
+    fn checked_area(&self) -> Option<isize> {
+        self.inner.checked_area()
+    }
+
+    fn area_f64(&self) -> f64 {
+        self.inner.area_f64()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.inner.perimeter()
+    }
+
+    fn default(&self, out: &mut dyn Output) {
+        self.inner.default(out);
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_spatial(&self) -> Option<&dyn Spatial> {
+        self.inner.as_spatial()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let cached = CachedShape::new(Square { height: 12 });
+    let areas: Vec<isize> = (0..3).map(|_| cached.area()).collect();
+    out.line(format!("areas: {:?} with {} hits", areas, cached.hits()));
+
+    cached.invalidate();
+    let area = cached.area();
+    out.line(format!("after invalidate: {} with {} hits", area, cached.hits()));
+    out.line(format!("inner side: {}", cached.inner().height));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    //A square that counts how many times its area was worked out. The counter is shared through
+    // an Rc so it can still be read after the square has been moved into a CachedShape.
+    #[derive(Clone)]
+    struct CountingSquare {
+        square: Square,
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl Shape for CountingSquare {
+        fn area(&self) -> isize {
+            self.calls.set(self.calls.get() + 1);
+            self.square.area()
+        }
+
+        fn perimeter(&self) -> f64 {
+            self.square.perimeter()
+        }
+
+        fn clone_box(&self) -> Box<dyn Shape> {
+            Box::new(self.clone())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn counting(height: isize) -> (CachedShape<CountingSquare>, Rc<Cell<usize>>) {
+        let calls = Rc::new(Cell::new(0));
+        let square = CountingSquare { square: Square { height }, calls: Rc::clone(&calls) };
+        (CachedShape::new(square), calls)
+    }
+
+    #[test]
+    fn area_is_computed_once() {
+        let (cached, calls) = counting(12);
+        assert_eq!(calls.get(), 0);
+        for _ in 0..5 {
+            assert_eq!(cached.area(), 144);
+        }
+        assert_eq!(calls.get(), 1);
+        assert_eq!(cached.hits(), 4);
+    }
+
+    #[test]
+    fn invalidate_recomputes_once() {
+        let (cached, calls) = counting(3);
+        cached.area();
+        cached.area();
+        cached.invalidate();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(cached.area(), 9);
+        assert_eq!(cached.area(), 9);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(cached.hits(), 2);
+    }
+
+    #[test]
+    fn checked_area_goes_to_the_inner_shape() {
+        let cached = CachedShape::new(Square { height: isize::MAX });
+        assert_eq!(cached.checked_area(), None);
+        assert_eq!(cached.hits(), 0);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{BENCH, SECTIONS};
+use std::path::PathBuf;
+
+//Everything that can be passed to the binary. The section name is only checked when it is run.
+// bench can be followed by how many shapes to time it over, like `bench 10000`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub section: Option<String>,
+    pub bench_size: Option<usize>,
+    pub csv: Option<PathBuf>,
+    pub interactive: bool,
+    pub quiz: bool,
+    pub render: Option<PathBuf>,
+    pub no_color: bool,
+    pub time: bool,
+}
+
+//Every flag as it shows up in the usage message.
+const FLAGS: [&str; 6] = [
+    "[--csv <path>]",
+    "[--interactive]",
+    "[--quiz]",
+    "[--render <path>]",
+    "[--no-color]",
+    "[--time]",
+];
+
+pub fn usage() -> String {
+    format!(
+        "usage: rust_book_chapter_10 [{}|{BENCH} [size]]\n       {}",
+        SECTIONS.join("|"),
+        FLAGS.join(" ")
+    )
+}
+
+//Takes the arguments without the program name, so std::env::args().skip(1).
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--csv" => match args.next() {
+                Some(path) => parsed.csv = Some(PathBuf::from(path)),
+                None => return Err(format!("missing path after --csv\n{}", usage())),
+            },
+            "--interactive" => parsed.interactive = true,
+            "--quiz" => parsed.quiz = true,
+            "--no-color" => parsed.no_color = true,
+            "--time" => parsed.time = true,
+            "--render" => match args.next() {
+                Some(path) => parsed.render = Some(PathBuf::from(path)),
+                None => return Err(format!("missing path after --render\n{}", usage())),
+            },
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown flag '{flag}'\n{}", usage()));
+            }
+            size if parsed.section.as_deref() == Some(BENCH) && parsed.bench_size.is_none() => {
+                match size.parse() {
+                    Ok(size) => parsed.bench_size = Some(size),
+                    Err(_) => return Err(format!("'{size}' is not a bench size\n{}", usage())),
+                }
+            }
+            _ if parsed.section.is_some() => {
+                return Err(format!("only one section can be run at a time\n{}", usage()));
+            }
+            _ => parsed.section = Some(arg),
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn bench_takes_an_optional_size() {
+        let args = parse(&["bench", "10000"]).unwrap();
+        assert_eq!(args.section.as_deref(), Some("bench"));
+        assert_eq!(args.bench_size, Some(10000));
+        assert_eq!(parse(&["bench"]).unwrap().bench_size, None);
+        assert_eq!(parse(&["--time", "bench", "5", "--no-color"]).unwrap().bench_size, Some(5));
+    }
+
+    #[test]
+    fn bench_size_has_to_be_a_number() {
+        let err = parse(&["bench", "lots"]).unwrap_err();
+        assert!(err.starts_with("'lots' is not a bench size\nusage:"), "{err}");
+        assert!(parse(&["bench", "-3"]).is_err());
+    }
+
+    #[test]
+    fn only_bench_takes_a_size() {
+        let err = parse(&["traits", "10"]).unwrap_err();
+        assert!(err.starts_with("only one section can be run at a time"), "{err}");
+        let err = parse(&["bench", "10", "20"]).unwrap_err();
+        assert!(err.starts_with("only one section can be run at a time"), "{err}");
+    }
+
+    #[test]
+    fn no_arguments_runs_everything() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+    }
+
+    #[test]
+    fn flags_can_go_anywhere() {
+        let args = parse(&["--csv", "out.csv", "traits", "--render", "a.ppm", "--quiz"]).unwrap();
+        assert_eq!(args.section.as_deref(), Some("traits"));
+        assert_eq!(args.csv, Some(PathBuf::from("out.csv")));
+        assert_eq!(args.render, Some(PathBuf::from("a.ppm")));
+        assert!(args.quiz && !args.interactive && !args.no_color && !args.time);
+        let args = parse(&["--interactive", "--no-color", "--time"]).unwrap();
+        assert!(args.interactive && args.no_color && args.time);
+        assert_eq!(args.section, None);
+    }
+
+    #[test]
+    fn paths_are_required_after_path_flags() {
+        let err = parse(&["--csv"]).unwrap_err();
+        assert!(err.starts_with("missing path after --csv\nusage:"), "{err}");
+        let err = parse(&["generics", "--render"]).unwrap_err();
+        assert!(err.starts_with("missing path after --render\nusage:"), "{err}");
+    }
+
+    #[test]
+    fn unknown_flags_are_rejected() {
+        let err = parse(&["--colour"]).unwrap_err();
+        assert!(err.starts_with("unknown flag '--colour'\nusage:"), "{err}");
+    }
+
+    #[test]
+    fn usage_lists_every_section_and_flag() {
+        let usage = usage();
+        for name in SECTIONS.iter().chain([&BENCH]) {
+            assert!(usage.contains(name), "{name} missing from {usage}");
+        }
+        for flag in FLAGS {
+            assert!(usage.contains(flag), "{flag} missing from {usage}");
+        }
+    }
+}
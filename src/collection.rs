@@ -0,0 +1,149 @@
+use crate::traits::{Container, Shape};
+
+//A thin wrapper around a Vec of trait objects. Everything it does goes through the Shape trait,
+// so it never needs to know which concrete shapes it is holding.
+//Deriving Clone works because of the Clone impl for Box<dyn Shape> in the traits module.
+#[derive(Clone)]
+pub struct ShapeCollection {
+    shapes: Vec<Box<dyn Shape>>,
+}
+
+impl ShapeCollection {
+    pub fn new() -> Self {
+        ShapeCollection { shapes: Vec::new() }
+    }
+
+    pub fn push(&mut self, shape: Box<dyn Shape>) {
+        self.shapes.push(shape);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Shape> {
+        self.shapes.iter().map(|shape| shape.as_ref())
+    }
+
+    //Largest area first. sort_by_key is a stable sort, so shapes with the same area stay in the
+    // order they were pushed.
+    pub fn sort_by_area(&mut self) {
+        self.shapes.sort_by_key(|shape| std::cmp::Reverse(shape.area()));
+    }
+
+    //If more than one shape has the largest area the first one pushed is returned.
+    pub fn largest(&self) -> Option<&dyn Shape> {
+        self.iter().reduce(|largest, shape| {
+            if shape.area() > largest.area() {
+                shape
+            } else {
+                largest
+            }
+        })
+    }
+}
+
+impl Container for ShapeCollection {
+    type Item = Box<dyn Shape>;
+
+    fn get(&self, i: usize) -> Option<&Box<dyn Shape>> {
+        self.shapes.get(i)
+    }
+
+    fn len(&self) -> usize {
+        self.shapes.len()
+    }
+}
+
+impl Default for ShapeCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{Square, Triangle};
+
+    fn collection(shapes: Vec<Box<dyn Shape>>) -> ShapeCollection {
+        let mut collection = ShapeCollection::new();
+        shapes.into_iter().for_each(|shape| collection.push(shape));
+        collection
+    }
+
+    fn areas(collection: &ShapeCollection) -> Vec<isize> {
+        collection.iter().map(|shape| shape.area()).collect()
+    }
+
+    #[test]
+    fn empty_collection() {
+        let empty = ShapeCollection::default();
+        assert!(empty.is_empty());
+        assert!(empty.largest().is_none());
+        assert!(Container::get(&empty, 0).is_none());
+    }
+
+    #[test]
+    fn sort_by_area_is_largest_first_and_stable() {
+        let mut shapes = collection(Vec::from([
+            Box::new(Triangle { base: 2, height: 2 }) as Box<dyn Shape>,
+            Box::new(Square { height: 3 }),
+            Box::new(Square { height: 1 }),
+            Box::new(Triangle { base: 1, height: 4 }),
+        ]));
+        shapes.sort_by_area();
+        assert_eq!(areas(&shapes), [9, 2, 2, 1]);
+        //The two area 2 triangles kept the order they were pushed in.
+        let kinds: Vec<bool> = shapes.iter().map(|s| s.as_any().is::<Triangle>()).collect();
+        assert_eq!(kinds, [false, true, true, false]);
+        let bases: Vec<isize> = shapes
+            .iter()
+            .filter_map(|shape| shape.as_any().downcast_ref::<Triangle>())
+            .map(|triangle| triangle.base)
+            .collect();
+        assert_eq!(bases, [2, 1]);
+    }
+
+    #[test]
+    fn largest_is_the_first_of_any_ties() {
+        let shapes = collection(Vec::from([
+            Box::new(Square { height: 2 }) as Box<dyn Shape>,
+            Box::new(Triangle { base: 2, height: 4 }),
+            Box::new(Square { height: 1 }),
+        ]));
+        let largest = shapes.largest().unwrap();
+        assert_eq!(largest.area(), 4);
+        assert!(largest.as_any().is::<Square>());
+    }
+
+    #[test]
+    fn clone_is_a_deep_copy() {
+        let mut original = collection(Vec::from([
+            Box::new(Square { height: 2 }) as Box<dyn Shape>,
+            Box::new(Triangle { base: 10, height: 10 }),
+        ]));
+        let copy = original.clone();
+        original.sort_by_area();
+        original.push(Box::new(Square { height: 3 }));
+        assert_eq!(areas(&original), [50, 4, 9]);
+        assert_eq!(areas(&copy), [4, 50]);
+    }
+
+    #[test]
+    fn mutating_a_shape_after_cloning_it_leaves_the_clone_alone() {
+        let mut square = Square { height: 2 };
+        let mut triangle = Triangle { base: 4, height: 6 };
+        let copies = collection(Vec::from([square.clone_box(), triangle.clone_box()]));
+        square.height = 100;
+        triangle.base = 0;
+        triangle.height = 0;
+        assert_eq!(areas(&copies), [4, 12]);
+        assert_eq!(areas(&copies.clone()), [4, 12]);
+        assert_eq!((square.area(), triangle.area()), (10_000, 0));
+    }
+}
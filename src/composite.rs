@@ -0,0 +1,168 @@
+use crate::output::Output;
+use crate::traits::{Circle, Shape, Square, Triangle};
+use std::any::Any;
+
+//A shape made out of other shapes. Because it is a Shape itself, a composite can be one of the
+// parts of another composite.
+#[derive(Clone)]
+pub struct CompositeShape {
+    parts: Vec<Box<dyn Shape>>,
+}
+
+impl CompositeShape {
+    pub fn new() -> Self {
+        CompositeShape { parts: Vec::new() }
+    }
+
+    pub fn push(&mut self, s: Box<dyn Shape>) {
+        self.parts.push(s);
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+}
+
+impl Default for CompositeShape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//The parts are assumed not to overlap, so the area is just the sum. A composite with no parts
+// has an area of 0.
+impl Shape for CompositeShape {
+    fn area(&self) -> isize {
+        self.parts.iter().map(|part| part.area()).sum()
+    }
+
+    fn checked_area(&self) -> Option<isize> {
+        self.parts.iter().try_fold(0isize, |total, part| total.checked_add(part.checked_area()?))
+    }
+
+    //Summing f64s starts from -0.0, which prints as -0 for a composite with no parts, so these
+    // fold from a plain 0.0 instead.
+    fn area_f64(&self) -> f64 {
+        self.parts.iter().fold(0.0, |total, part| total + part.area_f64())
+    }
+
+    //Every part's outline added up. Parts that touch would share an edge that isn't really on
+    // the outside, so for those this is more than the true perimeter.
+    fn perimeter(&self) -> f64 {
+        self.parts.iter().fold(0.0, |total, part| total + part.perimeter())
+    }
+
+    fn default(&self, out: &mut dyn Output) {
+        out.line(format!("Composite of {} parts default called.", self.parts.len()));
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+//Iterating hands out the parts in the order they were pushed. Only the top level is walked, a
+// nested composite comes out as a single part.
+impl IntoIterator for CompositeShape {
+    type Item = Box<dyn Shape>;
+    type IntoIter = std::vec::IntoIter<Box<dyn Shape>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.parts.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CompositeShape {
+    type Item = &'a Box<dyn Shape>;
+    type IntoIter = std::slice::Iter<'a, Box<dyn Shape>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.parts.iter()
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut house = CompositeShape::new();
+    house.push(Box::new(Square { height: 10 }));
+    house.push(Box::new(Triangle { base: 5, height: 10 }));
+    out.line(format!("house area: {}", house.area()));
+    house.default(out);
+
+    let mut street = CompositeShape::new();
+    street.push(Box::new(house));
+    street.push(Box::new(Circle { radius: 1.0 }));
+    street.push(Box::new(CompositeShape::new()));
+    out.line(format!("street area: {} ({})", street.area(), street.area_f64()));
+    let part_areas: Vec<isize> = (&street).into_iter().map(|part| part.area()).collect();
+    out.line(format!("street part areas: {:?}", part_areas));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn house() -> CompositeShape {
+        let mut house = CompositeShape::new();
+        house.push(Box::new(Square { height: 10 }));
+        house.push(Box::new(Triangle { base: 5, height: 10 }));
+        house
+    }
+
+    #[test]
+    fn empty_composite_has_no_area() {
+        let empty = CompositeShape::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.area(), 0);
+        assert_eq!(empty.checked_area(), Some(0));
+        assert_eq!(empty.area_f64(), 0.0);
+    }
+
+    #[test]
+    fn area_is_the_sum_of_the_parts() {
+        assert_eq!(house().area(), 125);
+        assert_eq!(house().len(), 2);
+    }
+
+    #[test]
+    fn nested_composites_count_every_level() {
+        let mut street = CompositeShape::new();
+        street.push(Box::new(house()));
+        street.push(Box::new(Circle { radius: 1.0 }));
+        assert_eq!(street.area(), 128);
+        assert_eq!(street.area_f64(), 125.0 + std::f64::consts::PI);
+        let part_areas: Vec<isize> = (&street).into_iter().map(|part| part.area()).collect();
+        assert_eq!(part_areas, [125, 3]);
+    }
+
+    #[test]
+    fn checked_area_fails_if_any_part_overflows_or_the_sum_does() {
+        let mut composite = house();
+        composite.push(Box::new(Square { height: isize::MAX }));
+        assert_eq!(composite.checked_area(), None);
+        let mut composite = CompositeShape::new();
+        composite.push(Box::new(Triangle { base: isize::MAX, height: 2 }));
+        composite.push(Box::new(Square { height: 1 }));
+        assert_eq!(composite.checked_area(), None);
+    }
+
+    #[test]
+    fn into_iter_hands_back_the_parts_in_order() {
+        let areas: Vec<isize> = house().into_iter().map(|part| part.area()).collect();
+        assert_eq!(areas, [100, 25]);
+    }
+
+    #[test]
+    fn default_says_how_many_parts() {
+        let mut out: Vec<String> = Vec::new();
+        house().default(&mut out);
+        assert_eq!(out, ["Composite of 2 parts default called."]);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::traits::Shape;
+use std::fmt::Display;
+
+//How b differs from a, so a positive delta means b is the bigger one.
+#[derive(Debug, PartialEq)]
+pub struct ShapeDiff {
+    pub area_delta: isize,
+    pub perimeter_delta: f64,
+    pub same_kind: bool,
+}
+
+//Two shapes are the same kind when they are the same concrete type underneath, which as_any can
+// tell even through a &dyn Shape.
+pub fn diff(a: &dyn Shape, b: &dyn Shape) -> ShapeDiff {
+    ShapeDiff {
+        area_delta: b.area() - a.area(),
+        perimeter_delta: b.perimeter() - a.perimeter(),
+        same_kind: a.as_any().type_id() == b.as_any().type_id(),
+    }
+}
+
+//For example `area +25, perimeter -2, different kinds`. The perimeter is rounded to two decimal
+// places so a hypotenuse doesn't drag a long tail of digits along with it.
+impl Display for ShapeDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let perimeter = (self.perimeter_delta * 100.0).round() / 100.0;
+        let kind = if self.same_kind { "same kind" } else { "different kinds" };
+        //Adding 0.0 turns a -0 from the rounding back into a plain 0.
+        write!(f, "area {:+}, perimeter {:+}, {kind}", self.area_delta, perimeter + 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{Circle, Square, Triangle};
+
+    #[test]
+    fn deltas_are_b_minus_a() {
+        let d = diff(&Square { height: 2 }, &Square { height: 5 });
+        assert_eq!(d, ShapeDiff { area_delta: 21, perimeter_delta: 12.0, same_kind: true });
+        let d = diff(&Square { height: 5 }, &Square { height: 2 });
+        assert_eq!((d.area_delta, d.perimeter_delta), (-21, -12.0));
+    }
+
+    #[test]
+    fn kind_comes_from_the_concrete_type() {
+        let square: Box<dyn Shape> = Box::new(Square { height: 2 });
+        let triangle: Box<dyn Shape> = Box::new(Triangle { base: 2, height: 4 });
+        assert!(!diff(square.as_ref(), triangle.as_ref()).same_kind);
+        assert!(diff(triangle.as_ref(), &Triangle { base: 1, height: 1 }).same_kind);
+    }
+
+    #[test]
+    fn display_shows_signs_and_rounds_the_perimeter() {
+        let d = diff(&Square { height: 3 }, &Triangle { base: 3, height: 4 });
+        assert_eq!(d.to_string(), "area -3, perimeter +0, different kinds");
+        let d = diff(&Circle { radius: 1.0 }, &Circle { radius: 2.0 });
+        assert_eq!(d.to_string(), "area +10, perimeter +6.28, same kind");
+        let same = diff(&Square { height: 1 }, &Square { height: 1 });
+        assert_eq!(same.to_string(), "area +0, perimeter +0, same kind");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::output::Output;
+use crate::rng::{random_shapes, Lcg};
+use crate::traits::{Shape, Square};
+use std::thread;
+use std::time::Instant;
+
+//Static dispatch. A copy of this function is made for every T it is called with, so the call to
+// area is known at compile time and can be inlined.
+pub fn sum_areas_static<T: Shape>(shapes: &[T]) -> isize {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+//Dynamic dispatch. There is only one copy of this function and every call to area goes through
+// the vtable of whatever is inside the Box.
+pub fn sum_areas_dynamic(shapes: &[Box<dyn Shape>]) -> isize {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+//thread::spawn needs the closure to be Send + 'static. Send because it moves to another thread,
+// and 'static because the thread could keep running after this function returns, so it can't
+// borrow anything from here. Each worker is given its own chunk of the Vec by value to get around
+// the second one, and the `+ Send` on the trait object is what makes the Box allowed to move
+// across. A plain Box<dyn Shape> could be holding something that isn't safe to send.
+//More threads than shapes just means some of them never get started, and 0 counts as 1.
+pub fn parallel_total_area(shapes: Vec<Box<dyn Shape + Send>>, threads: usize) -> isize {
+    let chunk_size = shapes.len().div_ceil(threads.max(1)).max(1);
+    let mut shapes = shapes.into_iter().peekable();
+    let mut workers = Vec::new();
+    while shapes.peek().is_some() {
+        let chunk: Vec<Box<dyn Shape + Send>> = shapes.by_ref().take(chunk_size).collect();
+        workers.push(thread::spawn(move || chunk.iter().map(|shape| shape.area()).sum::<isize>()));
+    }
+    workers
+        .into_iter()
+        .map(|worker| worker.join().expect("an area worker panicked"))
+        .sum()
+}
+
+pub const BENCH_THREADS: usize = 4;
+pub const BENCH_SEED: u64 = 42;
+
+//Times both versions over `size` squares. The timings are only really meaningful with
+// `cargo run --release -- bench`.
+pub fn bench(size: usize, out: &mut dyn Output) {
+    let squares: Vec<Square> = (0..size).map(|i| Square { height: (i % 100) as isize }).collect();
+    let boxed: Vec<Box<dyn Shape>> = (0..size)
+        .map(|i| Box::new(Square { height: (i % 100) as isize }) as Box<dyn Shape>)
+        .collect();
+
+    let start = Instant::now();
+    let static_total = sum_areas_static(&squares);
+    let static_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let dynamic_total = sum_areas_dynamic(&boxed);
+    let dynamic_elapsed = start.elapsed();
+
+    out.line(format!("static dispatch: total {static_total} in {:?}", static_elapsed));
+    out.line(format!("dynamic dispatch: total {dynamic_total} in {:?}", dynamic_elapsed));
+
+    let sendable: Vec<Box<dyn Shape + Send>> = (0..size)
+        .map(|i| Box::new(Square { height: (i % 100) as isize }) as Box<dyn Shape + Send>)
+        .collect();
+    let start = Instant::now();
+    let parallel_total = parallel_total_area(sendable, BENCH_THREADS);
+    let parallel_elapsed = start.elapsed();
+    out.line(format!(
+        "parallel over {BENCH_THREADS} threads: total {parallel_total} in {:?}",
+        parallel_elapsed
+    ));
+
+    //A mix of squares and triangles, so the vtable call can't always go to the same place.
+    let mixed = random_shapes(&mut Lcg::new(BENCH_SEED), size);
+    let start = Instant::now();
+    let mixed_total = sum_areas_dynamic(&mixed);
+    let mixed_elapsed = start.elapsed();
+    out.line(format!(
+        "dynamic dispatch over random shapes: total {mixed_total} in {:?}",
+        mixed_elapsed
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Triangle;
+
+    fn sendable(sides: impl Iterator<Item = isize>) -> Vec<Box<dyn Shape + Send>> {
+        sides.map(|height| Box::new(Square { height }) as Box<dyn Shape + Send>).collect()
+    }
+
+    #[test]
+    fn static_and_dynamic_give_the_same_total() {
+        let squares: Vec<Square> = (1..=4).map(|height| Square { height }).collect();
+        let boxed: Vec<Box<dyn Shape>> = squares.iter().map(|square| square.clone_box()).collect();
+        assert_eq!(sum_areas_static(&squares), 30);
+        assert_eq!(sum_areas_dynamic(&boxed), 30);
+        assert_eq!(sum_areas_static::<Triangle>(&[]), 0);
+    }
+
+    #[test]
+    fn parallel_total_matches_for_any_thread_count() {
+        for threads in [0, 1, 3, 4, 100] {
+            assert_eq!(parallel_total_area(sendable(1..=10), threads), 385, "{threads} threads");
+        }
+        assert_eq!(parallel_total_area(Vec::new(), 4), 0);
+    }
+
+    #[test]
+    fn bench_prints_the_same_total_for_every_version() {
+        let mut out: Vec<String> = Vec::new();
+        bench(100, &mut out);
+        assert_eq!(out.len(), 4);
+        for line in &out[..3] {
+            assert!(line.contains("total 328350 in "), "{line}");
+        }
+        assert!(out[3].starts_with("dynamic dispatch over random shapes: total "));
+    }
+}
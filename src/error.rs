@@ -0,0 +1,193 @@
+use crate::builder::BuildError;
+use crate::json::JsonError;
+use crate::parse::ParseShapeError;
+use crate::traits::{OverflowError, ShapeError};
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+
+//One error type for anything in the crate that can fail, so a function doing several fallible
+// things can use ? on all of them. Each variant keeps the original error inside it.
+pub enum ChapterError {
+    Shape(ShapeError),
+    Parse(ParseShapeError),
+    Json(JsonError),
+    Build(BuildError),
+    Overflow(OverflowError),
+    Io(io::Error),
+    Usage(String),
+    Context(&'static str, Box<ChapterError>),
+}
+
+impl ChapterError {
+    //Wraps the error with a note about what was going on when it happened. Notes can be stacked,
+    // the outermost one is printed first.
+    pub fn context(self, context: &'static str) -> Self {
+        ChapterError::Context(context, Box::new(self))
+    }
+}
+
+//The same as calling context on the error, but for a Result with any error that can become a
+// ChapterError. It saves writing `.map_err(|e| ChapterError::from(e).context(..))` every time.
+pub trait WithContext<T> {
+    fn context(self, context: &'static str) -> Result<T, ChapterError>;
+}
+
+impl<T, E: Into<ChapterError>> WithContext<T> for Result<T, E> {
+    fn context(self, context: &'static str) -> Result<T, ChapterError> {
+        self.map_err(|e| e.into().context(context))
+    }
+}
+
+impl Display for ChapterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChapterError::Shape(e) => write!(f, "invalid shape: {e}"),
+            ChapterError::Parse(e) => write!(f, "could not parse shape: {e}"),
+            ChapterError::Json(e) => write!(f, "could not read json: {e}"),
+            ChapterError::Build(e) => write!(f, "could not build shape: {e}"),
+            ChapterError::Overflow(e) => write!(f, "{e}"),
+            ChapterError::Io(e) => write!(f, "io error: {e}"),
+            ChapterError::Usage(message) => write!(f, "{message}"),
+            ChapterError::Context(context, e) => write!(f, "{context}: {e}"),
+        }
+    }
+}
+
+//Returning an error from main prints it with Debug, so Debug is written to print the same
+// friendly message as Display instead of the derived `Io(Os { code: 2, .. })`.
+impl std::fmt::Debug for ChapterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+//source hands back the error that was wrapped so the whole chain can be walked. A usage error
+// didn't come from anything else. A context note isn't an error of its own, so its source is
+// the error it was wrapped around.
+impl Error for ChapterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ChapterError::Shape(e) => Some(e),
+            ChapterError::Parse(e) => Some(e),
+            ChapterError::Json(e) => Some(e),
+            ChapterError::Build(e) => Some(e),
+            ChapterError::Overflow(e) => Some(e),
+            ChapterError::Io(e) => Some(e),
+            ChapterError::Usage(_) => None,
+            ChapterError::Context(_, e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl From<ShapeError> for ChapterError {
+    fn from(e: ShapeError) -> Self {
+        ChapterError::Shape(e)
+    }
+}
+
+impl From<ParseShapeError> for ChapterError {
+    fn from(e: ParseShapeError) -> Self {
+        ChapterError::Parse(e)
+    }
+}
+
+impl From<JsonError> for ChapterError {
+    fn from(e: JsonError) -> Self {
+        ChapterError::Json(e)
+    }
+}
+
+impl From<BuildError> for ChapterError {
+    fn from(e: BuildError) -> Self {
+        ChapterError::Build(e)
+    }
+}
+
+impl From<OverflowError> for ChapterError {
+    fn from(e: OverflowError) -> Self {
+        ChapterError::Overflow(e)
+    }
+}
+
+impl From<io::Error> for ChapterError {
+    fn from(e: io::Error) -> Self {
+        ChapterError::Io(e)
+    }
+}
+
+//The error's own message and then one `  caused by:` line for every source under it, innermost
+// last. Works for any error, not just ChapterError.
+pub fn error_chain(e: &dyn Error) -> Vec<String> {
+    let mut lines = Vec::from([e.to_string()]);
+    let mut source = e.source();
+    while let Some(cause) = source {
+        lines.push(format!("  caused by: {cause}"));
+        source = cause.source();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::ShapeBuilder;
+    use crate::traits::{Square, Triangle};
+
+    #[test]
+    fn each_error_converts_with_its_own_prefix() {
+        let shape: ChapterError = Square::new(0).unwrap_err().into();
+        assert_eq!(shape.to_string(), "invalid shape: height must be positive, got 0");
+        let parse: ChapterError = "circle:3".parse::<Square>().unwrap_err().into();
+        assert_eq!(parse.to_string(), "could not parse shape: unknown shape 'circle'");
+        let json: ChapterError = Triangle::from_json("{").unwrap_err().into();
+        assert_eq!(json.to_string(), "could not read json: unexpected end of input");
+        let build: ChapterError = ShapeBuilder::square().build().unwrap_err().into();
+        assert_eq!(build.to_string(), "could not build shape: no height was given");
+        let overflow: ChapterError = OverflowError.into();
+        assert_eq!(overflow.to_string(), "the area is too big to fit in an isize");
+    }
+
+    #[test]
+    fn question_mark_converts_into_chapter_error() {
+        fn both(height: isize) -> Result<isize, ChapterError> {
+            let square = Square::new(height)?;
+            let built = ShapeBuilder::triangle().base(height).height(height).build()?;
+            Ok(square.height + built.area())
+        }
+        assert_eq!(both(2).unwrap(), 4);
+        assert!(matches!(both(-1), Err(ChapterError::Shape(_))));
+    }
+
+    #[test]
+    fn debug_prints_the_same_as_display() {
+        let e = ChapterError::Usage(String::from("no such section")).context("while starting");
+        assert_eq!(format!("{e:?}"), "while starting: no such section");
+        assert_eq!(format!("{e:?}"), e.to_string());
+    }
+
+    #[test]
+    fn context_notes_stack_outermost_first() {
+        let result: Result<(), io::Error> = Err(io::Error::other("disk full"));
+        let e = result.context("while saving").unwrap_err().context("while exporting");
+        assert_eq!(e.to_string(), "while exporting: while saving: io error: disk full");
+    }
+
+    #[test]
+    fn error_chain_has_a_caused_by_line_per_source() {
+        let e = ChapterError::from(Square::new(-3).unwrap_err()).context("while reading");
+        let expected = [
+            "while reading: invalid shape: height must be positive, got -3",
+            "  caused by: invalid shape: height must be positive, got -3",
+            "  caused by: height must be positive, got -3",
+        ];
+        assert_eq!(error_chain(&e), expected);
+    }
+
+    #[test]
+    fn error_chain_of_an_error_without_a_source_is_one_line() {
+        let e = ChapterError::Usage(String::from("usage: chapter10 [section]"));
+        assert_eq!(error_chain(&e), ["usage: chapter10 [section]"]);
+        assert_eq!(error_chain(&OverflowError), ["the area is too big to fit in an isize"]);
+    }
+}
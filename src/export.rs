@@ -0,0 +1,147 @@
+use crate::error::ChapterError;
+use crate::traits::{Circle, Shape, Square, Triangle};
+use std::fs;
+use std::path::Path;
+
+//The same shapes the traits section starts with, each with a name to go in the first column.
+pub fn demo_shapes() -> Vec<(String, Box<dyn Shape>)> {
+    vec![
+        (String::from("triangle"), Box::new(Triangle { base: 5, height: 10 })),
+        (String::from("square"), Box::new(Square { height: 10 })),
+        (String::from("circle"), Box::new(Circle { radius: 2.0 })),
+        (String::from("square, scaled \"3x\""), Box::new(Square { height: 30 })),
+    ]
+}
+
+//A field only gets quotes when it needs them, which is when it holds a comma, a quote or a line
+// break. Quotes inside a quoted field are doubled up.
+fn quote_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+//The header is always there, so no shapes gives back just the header line.
+pub fn export_csv(shapes: &[(String, Box<dyn Shape>)]) -> String {
+    let mut csv = String::from("name,area,perimeter\n");
+    for (name, shape) in shapes {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            quote_field(name),
+            shape.area_f64(),
+            shape.perimeter()
+        ));
+    }
+    csv
+}
+
+pub fn write_csv(path: &Path, shapes: &[(String, Box<dyn Shape>)]) -> Result<(), ChapterError> {
+    fs::write(path, export_csv(shapes))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //A right triangle with whole sides, so every column comes out as a short exact number.
+    fn named(name: &str) -> (String, Box<dyn Shape>) {
+        (String::from(name), Box::new(Triangle { base: 3, height: 4 }))
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("chapter10-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn no_shapes_is_just_the_header() {
+        assert_eq!(export_csv(&[]), "name,area,perimeter\n");
+    }
+
+    #[test]
+    fn one_row_per_shape() {
+        let csv = export_csv(&[named("first"), named("second")]);
+        assert_eq!(csv, "name,area,perimeter\nfirst,6,12\nsecond,6,12\n");
+    }
+
+    #[test]
+    fn fields_that_need_it_are_quoted() {
+        assert_eq!(quote_field("plain"), "plain");
+        assert_eq!(quote_field("a,b"), "\"a,b\"");
+        assert_eq!(quote_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_field("two\nlines"), "\"two\nlines\"");
+        let csv = export_csv(&[named("big, \"red\"")]);
+        assert_eq!(csv.lines().nth(1), Some("\"big, \"\"red\"\"\",6,12"));
+    }
+
+    #[test]
+    fn write_csv_writes_the_same_text_to_the_file() {
+        let path = temp_path("shapes.csv");
+        let shapes = demo_shapes();
+        write_csv(&path, &shapes).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(written, export_csv(&shapes));
+    }
+
+    #[test]
+    fn write_csv_reports_a_path_it_cannot_write() {
+        let path = temp_path("missing-dir").join("shapes.csv");
+        let err = write_csv(&path, &demo_shapes()).unwrap_err();
+        assert!(matches!(err, ChapterError::Io(_)), "{err}");
+        assert!(!path.exists());
+    }
+
+    //Just enough of a CSV reader to read back what export_csv writes. A quote starts or ends a
+    // quoted field, and two quotes in a row inside one are a single quote.
+    fn split_csv(text: &str) -> Vec<Vec<String>> {
+        let (mut rows, mut row, mut field) = (Vec::new(), Vec::new(), String::new());
+        let (mut quoted, mut chars) = (false, text.chars().peekable());
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => row.push(std::mem::take(&mut field)),
+                '\n' if !quoted => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                c => field.push(c),
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn the_splitter_reads_quoted_fields() {
+        let rows = split_csv("a,\"b,c\"\n\"say \"\"hi\"\"\",\"x\ny\"\n");
+        assert_eq!(rows, [vec!["a", "b,c"], vec!["say \"hi\"", "x\ny"]]);
+    }
+
+    #[test]
+    fn exported_csv_reads_back_the_same() {
+        let mut shapes = demo_shapes();
+        for name in ["", "comma, here", "\"quoted\"", "two\nlines", "trailing,"] {
+            shapes.push(named(name));
+        }
+        let rows = split_csv(&export_csv(&shapes));
+        assert_eq!(rows[0], ["name", "area", "perimeter"]);
+        assert_eq!(rows.len(), shapes.len() + 1);
+        for ((name, shape), row) in shapes.iter().zip(&rows[1..]) {
+            assert_eq!(row.len(), 3, "{row:?}");
+            assert_eq!(&row[0], name);
+            assert_eq!(row[1].parse::<f64>().unwrap(), shape.area_f64());
+            assert_eq!(row[2].parse::<f64>().unwrap(), shape.perimeter());
+        }
+    }
+
+    #[test]
+    fn no_shapes_reads_back_as_only_the_header() {
+        assert_eq!(split_csv(&export_csv(&[])), [["name", "area", "perimeter"]]);
+    }
+}
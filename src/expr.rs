@@ -0,0 +1,301 @@
+use crate::output::Output;
+use crate::registry::ShapeRegistry;
+use crate::traits::{Square, Triangle};
+use std::fmt::Display;
+
+//Identifiers and numbers are slices of the input instead of owned Strings, so a Token can't
+// outlive the string it was made from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token<'a> {
+    Ident(&'a str),
+    Number(&'a str),
+    Plus,
+    Star,
+    LParen,
+    RParen,
+}
+
+//The offset is in bytes, the same as the index used to slice a str.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenError {
+    InvalidChar { ch: char, offset: usize },
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TokenError::InvalidChar { ch, offset } => {
+                write!(f, "invalid character '{ch}' at byte {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+//The end of an identifier or number is wherever the first character that doesn't fit is.
+fn end_of(input: &str, start: usize, fits: impl Fn(char) -> bool) -> usize {
+    input[start..].find(|c: char| !fits(c)).map_or(input.len(), |len| start + len)
+}
+
+pub fn tokenize<'a>(input: &'a str) -> Result<Vec<Token<'a>>, TokenError> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    while let Some(ch) = input[offset..].chars().next() {
+        let (token, end) = match ch {
+            c if c.is_whitespace() => {
+                offset += c.len_utf8();
+                continue;
+            }
+            '+' => (Token::Plus, offset + 1),
+            '*' => (Token::Star, offset + 1),
+            '(' => (Token::LParen, offset + 1),
+            ')' => (Token::RParen, offset + 1),
+            c if c.is_ascii_digit() => {
+                let end = end_of(input, offset, |c| c.is_ascii_digit());
+                (Token::Number(&input[offset..end]), end)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let end = end_of(input, offset, |c| c.is_alphanumeric() || c == '_');
+                (Token::Ident(&input[offset..end]), end)
+            }
+            c => return Err(TokenError::InvalidChar { ch: c, offset }),
+        };
+        tokens.push(token);
+        offset = end;
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    Token(TokenError),
+    UnknownShape(String),
+    UnknownFunction(String),
+    Unexpected(String),
+    UnexpectedEnd,
+    Overflow,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EvalError::Token(e) => write!(f, "{e}"),
+            EvalError::UnknownShape(name) => write!(f, "no shape named '{name}'"),
+            EvalError::UnknownFunction(name) => write!(f, "unknown function '{name}'"),
+            EvalError::Unexpected(token) => write!(f, "unexpected {token}"),
+            EvalError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            EvalError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+impl From<TokenError> for EvalError {
+    fn from(e: TokenError) -> Self {
+        EvalError::Token(e)
+    }
+}
+
+//Two lifetimes again. 't is how long the slice of tokens is borrowed for and 'a is how long the
+// input the tokens point into lives. The parser only needs the tokens while it runs, but the
+// &'a str inside them could be handed out for as long as the input is around.
+struct Parser<'t, 'a> {
+    tokens: &'t [Token<'a>],
+    pos: usize,
+    shapes: &'t ShapeRegistry,
+}
+
+//expr   = term ('+' term)*
+//term   = factor ('*' factor)*
+//factor = number | ident '(' ident ')' | '(' expr ')'
+//Splitting term out of expr is what makes * bind tighter than +.
+impl<'t, 'a> Parser<'t, 'a> {
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).copied();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: Token<'a>) -> Result<(), EvalError> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(EvalError::Unexpected(format!("{:?}", token))),
+            None => Err(EvalError::UnexpectedEnd),
+        }
+    }
+
+    fn ident(&mut self) -> Result<&'a str, EvalError> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(name),
+            Some(token) => Err(EvalError::Unexpected(format!("{:?}", token))),
+            None => Err(EvalError::UnexpectedEnd),
+        }
+    }
+
+    fn expr(&mut self) -> Result<isize, EvalError> {
+        let mut total = self.term()?;
+        while self.peek() == Some(Token::Plus) {
+            self.pos += 1;
+            let rhs = self.term()?;
+            total = total.checked_add(rhs).ok_or(EvalError::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    fn term(&mut self) -> Result<isize, EvalError> {
+        let mut product = self.factor()?;
+        while self.peek() == Some(Token::Star) {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            product = product.checked_mul(rhs).ok_or(EvalError::Overflow)?;
+        }
+        Ok(product)
+    }
+
+    //A number too big for an isize is an overflow rather than something that isn't a number,
+    // the tokenizer already made sure it is only digits.
+    fn factor(&mut self) -> Result<isize, EvalError> {
+        match self.next() {
+            Some(Token::Number(digits)) => digits.parse().map_err(|_| EvalError::Overflow),
+            Some(Token::Ident("area")) => {
+                self.expect(Token::LParen)?;
+                let name = self.ident()?;
+                self.expect(Token::RParen)?;
+                self.shapes
+                    .area_of(name)
+                    .ok_or_else(|| EvalError::UnknownShape(name.to_string()))
+            }
+            Some(Token::Ident(name)) => Err(EvalError::UnknownFunction(name.to_string())),
+            Some(Token::LParen) => {
+                let value = self.expr()?;
+                self.expect(Token::RParen)?;
+                Ok(value)
+            }
+            Some(token) => Err(EvalError::Unexpected(format!("{:?}", token))),
+            None => Err(EvalError::UnexpectedEnd),
+        }
+    }
+}
+
+//Evaluates something like `area(sq) + 2 * area(tri)` against the shapes in the registry. There
+// is no minus or divide, so the only arithmetic error is overflow.
+pub fn eval(expr: &str, shapes: &ShapeRegistry) -> Result<isize, EvalError> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser { tokens: &tokens, pos: 0, shapes };
+    let value = parser.expr()?;
+    match parser.next() {
+        Some(token) => Err(EvalError::Unexpected(format!("{:?}", token))),
+        None => Ok(value),
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let input = String::from("area(sq) + 2 * area(tri)");
+    match tokenize(&input) {
+        Ok(tokens) => out.line(format!("tokens: {:?}", tokens)),
+        Err(e) => out.line(format!("error: {e}")),
+    }
+
+    let mut shapes = ShapeRegistry::new();
+    shapes.register("sq", Box::new(Square { height: 10 }));
+    shapes.register("tri", Box::new(Triangle { base: 5, height: 10 }));
+
+    let expressions = [
+        "area(sq) + 2 * area(tri)",
+        "(area(sq) + 2) * area(tri)",
+        "2 * 3 + 4 * 5",
+        "area(circle)",
+        "volume(sq)",
+        "area(sq) - 1",
+        "area(sq) + ✓",
+        "9223372036854775807 + 1",
+        "99999999999999999999",
+        "(1 + 2",
+        "1 + 2)",
+    ];
+    for expr in expressions {
+        match eval(expr, &shapes) {
+            Ok(value) => out.line(format!("{expr} = {value}")),
+            Err(e) => out.line(format!("{expr}: {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shapes() -> ShapeRegistry {
+        let mut shapes = ShapeRegistry::new();
+        shapes.register("sq", Box::new(Square { height: 10 }));
+        shapes.register("tri", Box::new(Triangle { base: 5, height: 10 }));
+        shapes
+    }
+
+    #[test]
+    fn tokens_borrow_from_the_input() {
+        let tokens = tokenize(" area(sq_1)+ 42*x ").unwrap();
+        let expected = [
+            Token::Ident("area"),
+            Token::LParen,
+            Token::Ident("sq_1"),
+            Token::RParen,
+            Token::Plus,
+            Token::Number("42"),
+            Token::Star,
+            Token::Ident("x"),
+        ];
+        assert_eq!(tokens, expected);
+        assert_eq!(tokenize("   "), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn invalid_characters_report_their_byte_offset() {
+        assert_eq!(tokenize("1 - 2"), Err(TokenError::InvalidChar { ch: '-', offset: 2 }));
+        //Offsets count bytes, not characters. é takes two, so with the space ✓ is at byte 3.
+        assert_eq!(tokenize("✓"), Err(TokenError::InvalidChar { ch: '✓', offset: 0 }));
+        assert_eq!(tokenize("é ✓"), Err(TokenError::InvalidChar { ch: '✓', offset: 3 }));
+    }
+
+    #[test]
+    fn star_binds_tighter_than_plus() {
+        let shapes = shapes();
+        assert_eq!(eval("2 * 3 + 4 * 5", &shapes), Ok(26));
+        assert_eq!(eval("2 * (3 + 4) * 5", &shapes), Ok(70));
+        assert_eq!(eval("area(sq) + 2 * area(tri)", &shapes), Ok(150));
+        assert_eq!(eval("(area(sq) + 2) * area(tri)", &shapes), Ok(2550));
+    }
+
+    #[test]
+    fn names_that_are_not_there_are_errors() {
+        let shapes = shapes();
+        assert_eq!(eval("area(circle)", &shapes), Err(EvalError::UnknownShape("circle".into())));
+        let err = eval("volume(sq)", &shapes);
+        assert_eq!(err, Err(EvalError::UnknownFunction("volume".into())));
+    }
+
+    #[test]
+    fn overflow_is_caught() {
+        let shapes = shapes();
+        assert_eq!(eval("9223372036854775807 + 1", &shapes), Err(EvalError::Overflow));
+        assert_eq!(eval("99999999999999999999", &shapes), Err(EvalError::Overflow));
+        assert_eq!(eval("4611686018427387904 * 2", &shapes), Err(EvalError::Overflow));
+    }
+
+    #[test]
+    fn badly_formed_expressions() {
+        let shapes = shapes();
+        assert_eq!(eval("", &shapes), Err(EvalError::UnexpectedEnd));
+        assert_eq!(eval("(1 + 2", &shapes), Err(EvalError::UnexpectedEnd));
+        assert_eq!(eval("1 + 2)", &shapes), Err(EvalError::Unexpected("RParen".into())));
+        assert_eq!(eval("area(1)", &shapes), Err(EvalError::Unexpected("Number(\"1\")".into())));
+        let err = eval("1 - 2", &shapes).unwrap_err();
+        assert_eq!(err.to_string(), "invalid character '-' at byte 2");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::output::Output;
+use std::fmt::Display;
+
+//The Shape trait in the traits module is hard coded to isize, this is the same idea but generic
+// over the number type. I tried making that trait generic instead, but it is used as `dyn Shape`
+// all over the place, and a trait object has to pick a single number type anyway, so every one
+// of those would have turned into `dyn Shape<isize>` for nothing. Composites, caches, the grid
+// and the renderer all mix shapes together, which only works if they share one number type.
+//So this is a separate, small hierarchy that exists to show the generic version side by side.
+// The names are the same on purpose, which is why it is always used through
+// `generic_shapes::` and never glob imported next to the traits module.
+
+//There are no external crates here, so this is a tiny stand in for a numeric trait. It only
+// has what the shapes below actually need.
+pub trait Num: Copy + Display {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+}
+
+impl Num for isize {
+    fn zero() -> Self {
+        0
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+}
+
+impl Num for i64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+}
+
+impl Num for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+}
+
+pub trait Shape<T: Num> {
+    fn area(&self) -> T;
+    fn perimeter(&self) -> T;
+}
+
+#[derive(Debug)]
+pub struct Square<T> {
+    pub height: T,
+}
+
+impl<T: Num> Shape<T> for Square<T> {
+    fn area(&self) -> T {
+        self.height.mul(self.height)
+    }
+
+    fn perimeter(&self) -> T {
+        let half = self.height.add(self.height);
+        half.add(half)
+    }
+}
+
+#[derive(Debug)]
+pub struct Rectangle<T> {
+    pub height: T,
+    pub width: T,
+}
+
+impl<T: Num> Shape<T> for Rectangle<T> {
+    fn area(&self) -> T {
+        self.height.mul(self.width)
+    }
+
+    fn perimeter(&self) -> T {
+        let half = self.height.add(self.width);
+        half.add(half)
+    }
+}
+
+//The same functions as the traits module, but the number type is now a generic as well.
+pub fn get_area<T: Num>(shape: &impl Shape<T>, out: &mut dyn Output) {
+    out.line(format!("Area is {}", shape.area()));
+}
+
+pub fn get_area_long<T: Num, S: Shape<T>>(shape: &S, out: &mut dyn Output) {
+    out.line(format!("Area is {}", shape.area()));
+}
+
+pub fn return_area<T: Num>(height: T) -> impl Shape<T> {
+    Square { height }
+}
+
+//zero() is what lets this start the sum without knowing the type.
+pub fn total_area<T: Num, S: Shape<T>>(shapes: &[S]) -> T {
+    shapes.iter().fold(T::zero(), |total, shape| total.add(shape.area()))
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    //Each of these gets its own copy of the code at compile time (monomorphization again).
+    let int_square: Square<isize> = Square { height: 10 };
+    let float_square: Square<f64> = Square { height: 2.5 };
+    let long_rectangle: Rectangle<i64> = Rectangle { height: 3, width: 4 };
+
+    get_area(&int_square, out);
+    get_area(&float_square, out);
+    get_area_long(&long_rectangle, out);
+    out.line(format!(
+        "isize square perimeter: {} f64 square perimeter: {}",
+        int_square.perimeter(),
+        float_square.perimeter()
+    ));
+    get_area(&return_area(1.5), out);
+    out.line(format!(
+        "total isize area: {} total f64 area: {}",
+        total_area(&[Square { height: 1isize }, Square { height: 2 }]),
+        total_area(&[Square { height: 0.5 }, Square { height: 1.5 }])
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_shapes() {
+        let square: Square<isize> = Square { height: 10 };
+        assert_eq!((square.area(), square.perimeter()), (100, 40));
+        let rectangle: Rectangle<i64> = Rectangle { height: 3, width: 4 };
+        assert_eq!((rectangle.area(), rectangle.perimeter()), (12, 14));
+    }
+
+    #[test]
+    fn float_shapes() {
+        let square = Square { height: 2.5 };
+        assert_eq!((square.area(), square.perimeter()), (6.25, 10.0));
+        let rectangle = Rectangle { height: 0.5, width: 3.0 };
+        assert_eq!((rectangle.area(), rectangle.perimeter()), (1.5, 7.0));
+    }
+
+    #[test]
+    fn total_area_works_for_every_number_type() {
+        assert_eq!(total_area(&[Square { height: 1isize }, Square { height: 2 }]), 5);
+        assert_eq!(total_area(&[Rectangle { height: 2i64, width: 5 }]), 10);
+        assert_eq!(total_area(&[Square { height: 0.5 }, Square { height: 1.5 }]), 2.5);
+        let none: [Square<f64>; 0] = [];
+        assert_eq!(total_area(&none), 0.0);
+    }
+
+    #[test]
+    fn returned_shape_keeps_the_number_type() {
+        assert_eq!(return_area(1.5).area(), 2.25);
+        assert_eq!(return_area(3isize).perimeter(), 12);
+    }
+
+    #[test]
+    fn get_area_prints_the_area() {
+        let mut out = Vec::new();
+        get_area(&Square { height: 1.5 }, &mut out);
+        get_area_long(&Rectangle { height: 2i64, width: 3 }, &mut out);
+        assert_eq!(out, ["Area is 2.25", "Area is 6"]);
+    }
+}
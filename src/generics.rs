@@ -0,0 +1,1342 @@
+use crate::generic_shapes;
+use crate::histogram;
+use crate::id;
+use crate::output::Output;
+use crate::report::SectionReport;
+use crate::timing;
+use crate::traits::Container;
+use crate::tree;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+//Generics can be used in functions. They have more restrictions than something like templates
+// in c++. But this allows the compiler to catch a lot more of the errors. This also requires
+// the PartialOrd trait to be explicitly stated. I assume this restriction is so the compiler
+// can catch errors with generics. The smaller value is returned, if the two are equal the
+// first one is handed back.
+pub fn smallest<T: PartialOrd>(one: T, two: T) -> T {
+    if two < one {
+        two
+    } else {
+        one
+    }
+}
+
+//The same thing without taking ownership. Both references need the same lifetime because
+// either one could be returned (see the lifetimes module).
+pub fn smallest_ref<'a, T: PartialOrd>(one: &'a T, two: &'a T) -> &'a T {
+    if two < one {
+        two
+    } else {
+        one
+    }
+}
+
+//Anything implementing PartialOrd works, including my own types.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+//Generics work on collections too. This hands back a reference to the largest element (the
+// first one if the largest shows up more than once) and None if there is nothing to look at.
+//This is where PartialOrd being `partial` matters. Any comparison with NaN is false, so a NaN
+// is never picked over another value, but if NaN comes first nothing is ever larger than it.
+pub fn largest<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    let mut iter = list.iter();
+    let mut largest = iter.next()?;
+    for item in iter {
+        if item > largest {
+            largest = item;
+        }
+    }
+    Some(largest)
+}
+
+//Both ends in a single pass over the slice. Checking every element against both the smallest
+// and the largest takes up to 2 comparisons each, so instead the elements are taken two at a
+// time. The pair is compared with each other first, then only the smaller one can be a new
+// smallest and only the larger one a new largest. That is 3 comparisons for every 2 elements.
+//Like largest, the first of any duplicates is the one kept. A NaN never becomes either end,
+// unless it is the very first element.
+pub fn min_max<T: PartialOrd>(items: &[T]) -> Option<(&T, &T)> {
+    let (first, rest) = items.split_first()?;
+    let (mut min, mut max) = (first, first);
+    let mut pairs = rest.chunks_exact(2);
+    for pair in &mut pairs {
+        let (a, b) = (&pair[0], &pair[1]);
+        let (small, large) = match b.partial_cmp(a) {
+            Some(Ordering::Less) => (b, a),
+            Some(Ordering::Greater) => (a, b),
+            Some(Ordering::Equal) => (a, a),
+            //Something that doesn't even equal itself is the NaN, so only the other one counts.
+            None if a.partial_cmp(a).is_none() => (b, b),
+            None => (a, a),
+        };
+        if small < min {
+            min = small;
+        }
+        if large > max {
+            max = large;
+        }
+    }
+    if let [last] = pairs.remainder() {
+        if last < min {
+            min = last;
+        } else if last > max {
+            max = last;
+        }
+    }
+    Some((min, max))
+}
+
+//Default gives a starting value to add onto, which is 0 for all the number types. An empty slice
+// sums to that starting value.
+//A plain `+` panics on overflow in a debug build and wraps around in a release build, so adding
+// 100i8 + 100i8 gives a different answer depending on how it was built. checked_sum below
+// gives back None instead, which is the same in both.
+pub fn sum<T>(items: &[T]) -> T
+where
+    T: Copy + Add<Output = T> + Default,
+{
+    items.iter().fold(T::default(), |total, &item| total + item)
+}
+
+//std has checked_add on every integer type, but not behind a trait that a generic could use. So
+// here is a small one.
+pub trait CheckedAdd: Sized {
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+impl CheckedAdd for i8 {
+    fn checked_add(self, other: i8) -> Option<i8> {
+        i8::checked_add(self, other)
+    }
+}
+
+impl CheckedAdd for i32 {
+    fn checked_add(self, other: i32) -> Option<i32> {
+        i32::checked_add(self, other)
+    }
+}
+
+impl CheckedAdd for i64 {
+    fn checked_add(self, other: i64) -> Option<i64> {
+        i64::checked_add(self, other)
+    }
+}
+
+impl CheckedAdd for isize {
+    fn checked_add(self, other: isize) -> Option<isize> {
+        isize::checked_add(self, other)
+    }
+}
+
+pub fn checked_sum<T>(items: &[T]) -> Option<T>
+where
+    T: Copy + CheckedAdd + Default,
+{
+    items.iter().try_fold(T::default(), |total, &item| total.checked_add(item))
+}
+
+//Adding everything up first and dividing at the end goes to infinity once the total passes
+// f64::MAX, even if every item is fine on its own. Keeping a running mean instead means the
+// value never gets bigger than the largest item. There is no mean of nothing, so empty is None.
+pub fn mean(items: &[f64]) -> Option<f64> {
+    if items.is_empty() {
+        return None;
+    }
+    let mut mean = 0.0;
+    for (i, item) in items.iter().enumerate() {
+        mean += (item - mean) / (i + 1) as f64;
+    }
+    Some(mean)
+}
+
+//Into<f64> covers the smaller integers and f32 as well as f64 itself. i64 and isize aren't
+// included since not every one of them can be turned into an f64 exactly.
+pub fn mean_of<T: Copy + Into<f64>>(items: &[T]) -> Option<f64> {
+    let items: Vec<f64> = items.iter().map(|&item| item.into()).collect();
+    mean(&items)
+}
+
+//The same contract as the binary_search_by_key on slices in std. items has to already be sorted
+// by the key f pulls out of them. Ok is the index of something with that key (any of them if
+// there are duplicates) and Err is where it would have to go to keep things sorted.
+pub fn binary_search_by_key<T, K: Ord, F: Fn(&T) -> K>(
+    items: &[T],
+    key: K,
+    f: F,
+) -> Result<usize, usize> {
+    let (mut lo, mut hi) = (0, items.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match f(&items[mid]).cmp(&key) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Ok(mid),
+        }
+    }
+    Err(lo)
+}
+
+//Keeps v sorted as long as it was sorted to begin with. An item equal to ones already there goes
+// after all of them, so equal items stay in the order they were inserted.
+pub fn insert_sorted<T: PartialOrd>(v: &mut Vec<T>, item: T) {
+    let index = v.partition_point(|existing| *existing <= item);
+    v.insert(index, item);
+}
+
+//Closures are generic too. Every closure has its own type, so the only way to accept one is a
+// generic bounded by one of the Fn traits. With n = 0 start comes straight back.
+pub fn apply_n_times<T, F: Fn(T) -> T>(start: T, n: usize, f: F) -> T {
+    let mut value = start;
+    for _ in 0..n {
+        value = f(value);
+    }
+    value
+}
+
+//None if nothing in items passes.
+pub fn first_passing<T, F: Fn(&T) -> bool>(items: &[T], pred: F) -> Option<&T> {
+    items.iter().find(|item| pred(item))
+}
+
+//Generics can also be used in structs.
+#[derive(Debug)]
+pub struct Rectangle<T> {
+    pub height: T,
+    pub width: T,
+}
+
+//The methods only exist for types that can actually be multiplied (and added for the
+// perimeter), which covers both the integer and float rectangles.
+impl<T: Mul<Output = T> + Copy> Rectangle<T> {
+    pub fn area(&self) -> T {
+        self.height * self.width
+    }
+
+    //A bound can also be added to a single method instead of the whole impl block.
+    pub fn perimeter(&self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        self.height + self.height + self.width + self.width
+    }
+}
+
+impl<T: PartialEq> Rectangle<T> {
+    pub fn is_square(&self) -> bool {
+        self.height == self.width
+    }
+}
+
+//Rectangles are compared by area, so a 2x6 and a 3x4 rectangle are equal even though they look
+// nothing alike. Deriving these would have compared height first and then width instead.
+impl<T: Mul<Output = T> + PartialEq + Copy> PartialEq for Rectangle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.area() == other.area()
+    }
+}
+
+//A NaN area can't be compared with anything, so partial_cmp gives back None instead of
+// panicking.
+impl<T: Mul<Output = T> + PartialOrd + Copy> PartialOrd for Rectangle<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.area().partial_cmp(&other.area())
+    }
+}
+
+//Only rectangles with a totally ordered T (the integers, not the floats) get Ord, which is what
+// max and sort need.
+impl<T: Mul<Output = T> + Eq + Copy> Eq for Rectangle<T> {}
+
+impl<T: Mul<Output = T> + Ord + Copy> Ord for Rectangle<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.area().cmp(&other.area())
+    }
+}
+
+//Both ends are included, so [1, 5] and [5, 9] overlap at 5 and [3, 3] is a single point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval<T: PartialOrd> {
+    lo: T,
+    hi: T,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IntervalError {
+    Reversed,
+    Unordered,
+}
+
+impl Display for IntervalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            IntervalError::Reversed => write!(f, "the low end is above the high end"),
+            IntervalError::Unordered => write!(f, "the ends can't be compared"),
+        }
+    }
+}
+
+impl std::error::Error for IntervalError {}
+
+impl<T: PartialOrd> Interval<T> {
+    //PartialOrd is only partial, so NaN can't be put in order with anything. That gets its own
+    // error rather than being called reversed.
+    pub fn new(lo: T, hi: T) -> Result<Self, IntervalError> {
+        match lo.partial_cmp(&hi) {
+            Some(Ordering::Greater) => Err(IntervalError::Reversed),
+            Some(_) => Ok(Interval { lo, hi }),
+            None => Err(IntervalError::Unordered),
+        }
+    }
+
+    pub fn lo(&self) -> &T {
+        &self.lo
+    }
+
+    pub fn hi(&self) -> &T {
+        &self.hi
+    }
+
+    pub fn contains(&self, v: &T) -> bool {
+        self.lo <= *v && *v <= self.hi
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.lo <= other.hi && other.lo <= self.hi
+    }
+
+    //The part both intervals have in common, if there is one.
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>>
+    where
+        T: Copy,
+    {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let lo = if other.lo > self.lo { other.lo } else { self.lo };
+        let hi = if other.hi < self.hi { other.hi } else { self.hi };
+        Some(Interval { lo, hi })
+    }
+}
+
+//Can use multiple generic type parameters.
+#[derive(Debug)]
+pub struct Triangle<T, U> {
+    pub base: T,
+    pub height: U,
+}
+
+//Can use generics in enums.
+#[derive(Debug, PartialEq)]
+pub enum PrimaryColors<T> {
+    Yellow(T),
+    Blue(T),
+    Red(T),
+}
+
+//None of these care what the payload is, only which variant it is.
+impl<T> PrimaryColors<T> {
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        match self {
+            PrimaryColors::Yellow(_) => (255, 255, 0),
+            PrimaryColors::Blue(_) => (0, 0, 255),
+            PrimaryColors::Red(_) => (255, 0, 0),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PrimaryColors::Yellow(_) => "yellow",
+            PrimaryColors::Blue(_) => "blue",
+            PrimaryColors::Red(_) => "red",
+        }
+    }
+
+    pub fn payload(&self) -> &T {
+        match self {
+            PrimaryColors::Yellow(value) => value,
+            PrimaryColors::Blue(value) => value,
+            PrimaryColors::Red(value) => value,
+        }
+    }
+
+    //The payload can change type while the variant stays the same, so the return type needs its
+    // own generic U.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> PrimaryColors<U> {
+        match self {
+            PrimaryColors::Yellow(value) => PrimaryColors::Yellow(f(value)),
+            PrimaryColors::Blue(value) => PrimaryColors::Blue(f(value)),
+            PrimaryColors::Red(value) => PrimaryColors::Red(f(value)),
+        }
+    }
+}
+
+//Written as the label followed by the payload, for example `blue:sky`.
+impl<T: Display> Display for PrimaryColors<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.label(), self.payload())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ColorParseError {
+    UnknownColor(String),
+    EmptyPayload,
+}
+
+impl Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ColorParseError::UnknownColor(name) => write!(f, "'{name}' isn't a primary color"),
+            ColorParseError::EmptyPayload => write!(f, "the color has no payload"),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+//The reverse of Display, so `blue:sky` parses back into Blue("sky"). The color name can be in
+// any case, but the payload is kept exactly as it was written. Everything after the first colon
+// is the payload, so it can have colons of its own. A missing colon counts as an empty payload.
+impl FromStr for PrimaryColors<String> {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, payload) = s.split_once(':').unwrap_or((s, ""));
+        let make = match name.to_lowercase().as_str() {
+            "yellow" => PrimaryColors::Yellow,
+            "blue" => PrimaryColors::Blue,
+            "red" => PrimaryColors::Red,
+            _ => return Err(ColorParseError::UnknownColor(name.to_string())),
+        };
+        if payload.is_empty() {
+            return Err(ColorParseError::EmptyPayload);
+        }
+        Ok(make(payload.to_string()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SecondaryColors {
+    Green,
+    Orange,
+    Purple,
+}
+
+impl Display for SecondaryColors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let label = match self {
+            SecondaryColors::Green => "green",
+            SecondaryColors::Orange => "orange",
+            SecondaryColors::Purple => "purple",
+        };
+        write!(f, "{label}")
+    }
+}
+
+//Matching on a tuple of both colors. The `_` patterns ignore the payloads entirely, so the two
+// colors don't even need the same payload type. Mixing a color with itself gives None.
+pub fn mix<T, U>(a: &PrimaryColors<T>, b: &PrimaryColors<U>) -> Option<SecondaryColors> {
+    use PrimaryColors::{Blue, Red, Yellow};
+
+    match (a, b) {
+        (Yellow(_), Blue(_)) | (Blue(_), Yellow(_)) => Some(SecondaryColors::Green),
+        (Yellow(_), Red(_)) | (Red(_), Yellow(_)) => Some(SecondaryColors::Orange),
+        (Blue(_), Red(_)) | (Red(_), Blue(_)) => Some(SecondaryColors::Purple),
+        _ => None,
+    }
+}
+
+//The same three colors without a payload, for when only the color itself matters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrimaryColor {
+    Yellow,
+    Blue,
+    Red,
+}
+
+impl PrimaryColor {
+    pub const ALL: [PrimaryColor; 3] =
+        [PrimaryColor::Yellow, PrimaryColor::Blue, PrimaryColor::Red];
+}
+
+impl Display for PrimaryColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let label = match self {
+            PrimaryColor::Yellow => "yellow",
+            PrimaryColor::Blue => "blue",
+            PrimaryColor::Red => "red",
+        };
+        write!(f, "{label}")
+    }
+}
+
+//Counting from 0, the nth color of Yellow, Blue, Red, Yellow, ...
+pub fn nth_color(n: usize) -> PrimaryColor {
+    PrimaryColor::ALL[n % PrimaryColor::ALL.len()]
+}
+
+//Goes Yellow, Blue, Red and then starts over. next never returns None, so anything that needs it
+// to stop has to use take (or take_n).
+pub struct ColorCycle {
+    position: usize,
+}
+
+impl ColorCycle {
+    pub fn new() -> Self {
+        ColorCycle { position: 0 }
+    }
+
+    pub fn take_n(n: usize) -> std::iter::Take<ColorCycle> {
+        ColorCycle::new().take(n)
+    }
+}
+
+impl Default for ColorCycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for ColorCycle {
+    type Item = PrimaryColor;
+
+    //Wrapping the position keeps it from overflowing no matter how long the cycle runs.
+    fn next(&mut self) -> Option<PrimaryColor> {
+        let color = nth_color(self.position);
+        self.position = (self.position + 1) % PrimaryColor::ALL.len();
+        Some(color)
+    }
+}
+
+//Generics can be used inside implemented methods on structs as well. The <T, U> defined after
+// the impl keyword are the values actually used inside the contained functions. They can be
+// different names than `Triangle` generic names.
+impl<T, U> Triangle<T, U> {
+    pub fn base(&self) -> &T {
+        &self.base
+    }
+
+    pub fn height(&self) -> &U {
+        &self.height
+    }
+
+    //The methods can bring in generics of their own too. swap flips the two types around and
+    // with_height swaps out just the height for something that might be a completely new type V.
+    pub fn swap(self) -> Triangle<U, T> {
+        Triangle {
+            base: self.height,
+            height: self.base,
+        }
+    }
+
+    pub fn with_height<V>(self, height: V) -> Triangle<T, V> {
+        Triangle {
+            base: self.base,
+            height,
+        }
+    }
+}
+
+//The generics can be set to specific types and the functions will only be used in the case
+// that those specific types are used. These can't reuse the names base or height now, because
+// Triangle<isize, isize> already gets those from the block above.
+impl Triangle<isize, isize> {
+    pub fn area(&self) -> isize {
+        self.base * self.height / 2
+    }
+}
+
+//The Point example from the book. x and y can be different types.
+#[derive(Debug)]
+pub struct Point<T, U> {
+    pub x: T,
+    pub y: U,
+}
+
+impl<T, U> Point<T, U> {
+    pub fn x(&self) -> &T {
+        &self.x
+    }
+
+    //V and W only belong to this method. The new point takes x from self and y from other, so
+    // the result mixes the generic types of both.
+    pub fn mixup<V, W>(self, other: Point<V, W>) -> Point<T, W> {
+        Point {
+            x: self.x,
+            y: other.y,
+        }
+    }
+}
+
+impl<T: Display, U: Display> Display for Point<T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+//Only points made of f32s get this method.
+impl Point<f32, f32> {
+    pub fn distance_from_origin(&self) -> f32 {
+        (self.x.powi(2) + self.y.powi(2)).sqrt()
+    }
+}
+
+//A generic data structure that can hold anything. The Vec does all the real work, the Stack just
+// limits it to pushing and popping from the top.
+#[derive(Debug)]
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+//Counting starts from the top of the stack, so first is the same as peek.
+impl<T> Container for Stack<T> {
+    type Item = T;
+
+    fn get(&self, i: usize) -> Option<&T> {
+        self.items.iter().rev().nth(i)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//Only stacks of things that can be displayed get print_all. Printed from the top down.
+impl<T: Display> Stack<T> {
+    pub fn print_all(&self, out: &mut dyn Output) {
+        for item in self.items.iter().rev() {
+            out.line(format!("{item}"));
+        }
+    }
+}
+
+//A tiny cache that keeps its entries in a Vec instead of a HashMap, so the keys only have to be
+// comparable instead of hashable. Looking something up is a linear search, which is fine for the
+// handful of entries it is meant for.
+#[derive(Debug)]
+pub struct Cache<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> Cache<K, V> {
+    pub fn new() -> Self {
+        Cache { entries: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialEq, V> Cache<K, V> {
+    //Inserting a key that is already there overwrites the value and hands back the old one, the
+    // same as HashMap::insert does.
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match self.entries.iter_mut().find(|(key, _)| *key == k) {
+            Some((_, value)) => Some(std::mem::replace(value, v)),
+            None => {
+                self.entries.push((k, v));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.entries.iter().find(|(key, _)| key == k).map(|(_, value)| value)
+    }
+}
+
+//Handing back the value itself instead of a reference is why V has to be Clone here. f is only
+// called when the key is missing.
+impl<K: PartialEq, V: Clone> Cache<K, V> {
+    pub fn get_or_insert_with(&mut self, k: K, f: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.get(&k) {
+            return value.clone();
+        }
+        let value = f();
+        self.entries.push((k, value.clone()));
+        value
+    }
+}
+
+//As far as the performance of generics, they seem to be determined at compile time and so they
+// don't make the program run any slower. There is a vocabulary word that I have never heard
+// before called `Monomorphization` which seems to mean filling in the types at compile time.
+
+pub fn run_demo(out: &mut dyn Output) -> SectionReport {
+    let mut report = SectionReport::new("generics");
+
+    report.demo("smallest");
+    out.line(format!("smallest: {}", smallest(1, 2)));
+    out.line(format!("smallest: {}", smallest('b', 'a')));
+
+    report.demo("smallest_ref");
+    let old_version = Version{major: 1, minor: 4};
+    let new_version = Version{major: 1, minor: 10};
+    out.line(format!("smallest_ref: {:?}", smallest_ref(&new_version, &old_version)));
+    out.line(format!("smallest_ref: {}", smallest_ref(&"same", &"same")));
+
+    report.demo("largest");
+    let number_list: Vec<i32> = Vec::from([34, 50, 25, 100, 65]);
+    let char_list: Vec<char> = Vec::from(['y', 'm', 'a', 'q']);
+    let float_list: Vec<f64> = Vec::from([1.5, -2.0, 8.25]);
+    let empty_list: Vec<i32> = Vec::new();
+    out.line(format!("largest: {:?}", largest(&number_list)));
+    report.record("largest", format!("{:?}", largest(&number_list)));
+    out.line(format!("largest: {:?}", largest(&char_list)));
+    out.line(format!("largest: {:?}", largest(&float_list)));
+    out.line(format!("largest: {:?}", largest(&empty_list)));
+    out.line(format!("largest: {:?}", largest(&[1.0, f64::NAN, 3.0])));
+    out.line(format!("largest: {:?}", largest(&[f64::NAN, 1.0, 3.0])));
+    report.demo("min_max");
+    out.line(format!("min_max: {:?}", min_max(&float_list)));
+    out.line(format!("min_max: {:?}", min_max(&char_list)));
+
+    report.demo("sum and mean");
+    let areas: Vec<i32> = [
+        Rectangle { height: 2, width: 3 },
+        Rectangle { height: 4, width: 4 },
+        Rectangle { height: 1, width: 7 },
+    ]
+    .iter()
+    .map(Rectangle::area)
+    .collect();
+    out.line(format!(
+        "areas {:?} sum to {} with a mean of {:?}",
+        areas,
+        sum(&areas),
+        mean_of(&areas)
+    ));
+    out.line(format!(
+        "checked sums of i8: {:?} and {:?}",
+        checked_sum(&[100i8, 27]),
+        checked_sum(&[100i8, 28])
+    ));
+    out.line(format!(
+        "mean of two f64::MAX: {:?} mean of nothing: {:?}",
+        mean(&[f64::MAX, f64::MAX]),
+        mean(&[])
+    ));
+
+    report.demo("sorted insertion");
+    let mut sorted_areas = Vec::new();
+    for (height, width) in [(3, 3), (1, 2), (5, 4), (2, 1), (1, 1)] {
+        insert_sorted(&mut sorted_areas, Rectangle { height, width }.area());
+        out.line(format!("sorted areas: {:?}", sorted_areas));
+    }
+    let by_area = [
+        Rectangle { height: 1, width: 1 },
+        Rectangle { height: 2, width: 2 },
+        Rectangle { height: 2, width: 4 },
+    ];
+    out.line(format!(
+        "area 4 is at {:?} and area 5 would go at {:?}",
+        binary_search_by_key(&by_area, 4, Rectangle::area),
+        binary_search_by_key(&by_area, 5, Rectangle::area)
+    ));
+
+    report.demo("interval");
+    let low = Interval::new(1, 5).expect("1 is below 5");
+    let high = Interval::new(5, 9).expect("5 is below 9");
+    out.line(format!(
+        "[1, 5] contains 5? {} overlaps [5, 9]? {} intersection: {:?}",
+        low.contains(&5),
+        low.overlaps(&high),
+        low.intersect(&high)
+    ));
+    let unit = Interval::new(0.0, 1.0).expect("0 is below 1");
+    let half = Interval::new(0.5, 2.5).expect("0.5 is below 2.5");
+    out.line(format!("[0, 1] and [0.5, 2.5] intersect at {:?}", unit.intersect(&half)));
+    match Interval::new(9, 1) {
+        Ok(interval) => out.line(format!("made {:?}", interval)),
+        Err(e) => out.line(format!("could not make [9, 1]: {e}")),
+    }
+
+    report.demo("closures");
+    let doubled = apply_n_times(generic_shapes::Square { height: 3 }, 3, |square| {
+        generic_shapes::Square { height: square.height * 2 }
+    });
+    out.line(format!("height 3 doubled three times: {}", doubled.height));
+    let rectangles = [
+        Rectangle { height: 2, width: 2 },
+        Rectangle { height: 3, width: 4 },
+        Rectangle { height: 5, width: 5 },
+    ];
+    let threshold = 10;
+    let big = first_passing(&rectangles, |rectangle| rectangle.area() > threshold);
+    out.line(format!("first rectangle over {threshold}: {:?}", big));
+    let huge = first_passing(&rectangles, |rectangle| rectangle.area() > 100);
+    out.line(format!("first rectangle over 100: {:?}", huge));
+
+    report.demo("rectangle");
+    let rect_one = Rectangle {
+        height: 1,
+        width: 2,
+    };
+    let rect_two = Rectangle {
+        height: 1.0,
+        width: 2.0,
+    };
+
+    out.line(format!("rect_one: {:?} rect_two: {:?}", rect_one, rect_two));
+    out.line(format!(
+        "rect_one area: {} perimeter: {} is square? {}",
+        rect_one.area(),
+        rect_one.perimeter(),
+        rect_one.is_square()
+    ));
+    report.record("rect_one area", rect_one.area());
+    out.line(format!(
+        "rect_two area: {} perimeter: {} is square? {}",
+        rect_two.area(),
+        rect_two.perimeter(),
+        rect_two.is_square()
+    ));
+    let same_area = Rectangle { height: 2, width: 6 } == Rectangle { height: 3, width: 4 };
+    out.line(format!("2x6 == 3x4? {same_area}"));
+    let rectangles = [
+        Rectangle { height: 2, width: 6 },
+        Rectangle { height: 5, width: 3 },
+        Rectangle { height: 1, width: 9 },
+    ];
+    out.line(format!("largest rectangle: {:?}", rectangles.iter().max()));
+    let nan = Rectangle { height: f64::NAN, width: 1.0 };
+    out.line(format!("comparing with NaN: {:?}", nan.partial_cmp(&rect_two)));
+
+    report.demo("triangle");
+    let triangle = Triangle {
+        base: 1,
+        height: 2.0,
+    };
+
+    out.line(format!("triangle: {:?}", triangle));
+    out.line(format!("base: {} height: {}", triangle.base(), triangle.height()));
+    let swapped = triangle.swap();
+    out.line(format!("swapped: {:?}", swapped));
+    let taller = swapped.with_height("very tall");
+    out.line(format!("with_height: {:?}", taller));
+    let isize_triangle: Triangle<isize, isize> = Triangle { base: 4, height: 6 };
+    out.line(format!("isize triangle area: {}", isize_triangle.area()));
+
+    report.demo("point");
+    let p1 = Point { x: 5, y: 10.4 };
+    let p2 = Point { x: "Hello", y: 'c' };
+    out.line(format!("p1.x: {}", p1.x()));
+    let p3 = p1.mixup(p2);
+    out.line(format!("p3.x = {}, p3.y = {}", p3.x, p3.y));
+    let float_point = Point { x: 3.0_f32, y: 4.0_f32 };
+    out.line(format!("distance from origin: {}", float_point.distance_from_origin()));
+
+    report.demo("primary colors");
+    let color = PrimaryColors::Yellow("Or are they RGB?");
+
+    out.line(format!("color: {:?}", color));
+    out.line(format!("color label: {} rgb: {:?}", color.label(), color.to_rgb()));
+    out.line(format!("color displayed: {color}"));
+    out.line(format!("color mapped: {:?}", color.map(|text| text.len())));
+    let red = PrimaryColors::Red(1);
+    let blue = PrimaryColors::Blue("sky");
+    match mix(&red, &blue) {
+        Some(secondary) => out.line(format!("{red} mixed with {blue} makes {secondary}")),
+        None => out.line(format!("{red} mixed with {blue} makes nothing new")),
+    }
+    out.line(format!("red mixed with red: {:?}", mix(&red, &PrimaryColors::Red(2))));
+
+    report.demo("parsing colors");
+    for text in ["blue:sky", "YELLOW:sun", "Red:time:12:00", "green:grass", "red:", "red"] {
+        match text.parse::<PrimaryColors<String>>() {
+            Ok(color) => {
+                let round_trip = color.to_string().parse::<PrimaryColors<String>>();
+                let same = round_trip.as_ref() == Ok(&color);
+                out.line(format!("'{text}' -> {color} (round trip ok: {same})"))
+            }
+            Err(e) => out.line(format!("'{text}': {e}")),
+        }
+    }
+
+    report.demo("color cycle");
+    let cycle: Vec<String> = ColorCycle::take_n(7).map(|color| color.to_string()).collect();
+    out.line(format!("cycle: {}", cycle.join(" ")));
+    out.line(format!(
+        "color 0: {} color 3: {} color 100: {}",
+        nth_color(0),
+        nth_color(3),
+        nth_color(100)
+    ));
+    let shapes = [
+        Rectangle { height: 1, width: 2 },
+        Rectangle { height: 3, width: 3 },
+        Rectangle { height: 2, width: 5 },
+        Rectangle { height: 4, width: 1 },
+    ];
+    for (shape, color) in shapes.iter().zip(ColorCycle::new()) {
+        out.line(format!("{color} rectangle with area {}", shape.area()));
+    }
+
+    report.demo("stack");
+    let mut numbers = Stack::new();
+    numbers.push(1);
+    numbers.push(2);
+    numbers.push(3);
+    out.line(format!("peek: {:?} len: {}", numbers.peek(), numbers.len()));
+    out.line(format!("pop: {:?} pop: {:?}", numbers.pop(), numbers.pop()));
+    let mut words = Stack::new();
+    words.push("bottom");
+    words.push("top");
+    words.print_all(out);
+    words.pop();
+    words.pop();
+    out.line(format!("pop on empty: {:?} is_empty: {}", words.pop(), words.is_empty()));
+
+    report.demo("cache");
+    let mut areas: Cache<String, i32> = Cache::new();
+    let mut calls = 0;
+    for name in ["small", "big", "small"] {
+        let area = areas.get_or_insert_with(String::from(name), || {
+            calls += 1;
+            let side = if name == "small" { 2 } else { 20 };
+            Rectangle { height: side, width: side }.area()
+        });
+        out.line(format!("{name} area: {area}"));
+    }
+    out.line(format!("cached {} areas with {} calls", areas.len(), calls));
+    report.record("cache calls", calls);
+    let old = areas.insert(String::from("small"), 5);
+    out.line(format!("overwrote {:?} with {:?}", old, areas.get(&String::from("small"))));
+
+    report.demo("histogram");
+    histogram::run_demo(out);
+
+    report.demo("typed ids");
+    id::run_demo(out);
+
+    report.demo("timing");
+    timing::run_demo(out);
+
+    report.demo("trees");
+    tree::run_demo(out);
+
+    report.demo("generic shapes");
+    generic_shapes::run_demo(out);
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    //Compares by value only and counts every comparison into a shared counter. The index is
+    // there to tell equal values apart, so a test can see which duplicate was kept.
+    #[derive(Debug)]
+    struct Counted<'a> {
+        value: i32,
+        index: usize,
+        comparisons: &'a Cell<usize>,
+    }
+
+    impl PartialEq for Counted<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.comparisons.set(self.comparisons.get() + 1);
+            self.value == other.value
+        }
+    }
+
+    impl PartialOrd for Counted<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            self.comparisons.set(self.comparisons.get() + 1);
+            self.value.partial_cmp(&other.value)
+        }
+    }
+
+    fn counted<'a>(values: &[i32], comparisons: &'a Cell<usize>) -> Vec<Counted<'a>> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| Counted { value, index, comparisons })
+            .collect()
+    }
+
+    //A value and where it was in the input.
+    type Found = (i32, usize);
+
+    //The smallest and largest, and how many comparisons it took to find them.
+    fn min_max_counted(values: &[i32]) -> Option<(Found, Found, usize)> {
+        let comparisons = Cell::new(0);
+        let items = counted(values, &comparisons);
+        let (min, max) = min_max(&items)?;
+        Some(((min.value, min.index), (max.value, max.index), comparisons.get()))
+    }
+
+    #[test]
+    fn min_max_of_nothing_is_none() {
+        assert_eq!(min_max::<i32>(&[]), None);
+        assert_eq!(min_max_counted(&[]), None);
+    }
+
+    #[test]
+    fn min_max_of_one_element_is_that_element_twice() {
+        assert_eq!(min_max(&[7]), Some((&7, &7)));
+        assert_eq!(min_max_counted(&[7]), Some(((7, 0), (7, 0), 0)));
+    }
+
+    #[test]
+    fn min_max_finds_both_ends() {
+        assert_eq!(min_max(&[3, -1, 8, 0, 8, -1]), Some((&-1, &8)));
+        assert_eq!(min_max(&['y', 'm', 'a', 'q']), Some((&'a', &'y')));
+        assert_eq!(min_max(&[2.5, f64::NAN, -0.5]), Some((&-0.5, &2.5)));
+    }
+
+    #[test]
+    fn min_max_keeps_the_first_of_equal_elements() {
+        let (min, max, _) = min_max_counted(&[4, 4, 4, 4, 4]).unwrap();
+        assert_eq!((min, max), ((4, 0), (4, 0)));
+        let (min, max, _) = min_max_counted(&[1, 9, 9, 1, 0, 0]).unwrap();
+        assert_eq!((min, max), ((0, 4), (9, 1)));
+    }
+
+    #[test]
+    fn min_max_takes_at_most_three_comparisons_per_two_elements() {
+        for n in 1..=50 {
+            let inputs = [
+                (0..n).collect::<Vec<i32>>(),
+                (0..n).rev().collect(),
+                (0..n).map(|i| if i % 2 == 0 { i } else { -i }).collect(),
+                Vec::from_iter(std::iter::repeat_n(5, n as usize)),
+            ];
+            for values in inputs {
+                let (_, _, comparisons) = min_max_counted(&values).unwrap();
+                assert!(2 * comparisons <= 3 * values.len(), "{comparisons} for {values:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn get_or_insert_with_only_calls_f_on_a_miss() {
+        let mut cache: Cache<&str, i32> = Cache::new();
+        let calls = Cell::new(0);
+        let compute = |value| {
+            calls.set(calls.get() + 1);
+            value
+        };
+        assert_eq!(cache.get_or_insert_with("a", || compute(1)), 1);
+        assert_eq!(calls.get(), 1);
+        //A hit hands back the stored value, not the one the closure would have made.
+        assert_eq!(cache.get_or_insert_with("a", || compute(99)), 1);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(cache.get_or_insert_with("b", || compute(2)), 2);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn insert_overwrites_and_hands_back_the_old_value() {
+        let mut cache = Cache::default();
+        assert!(cache.is_empty());
+        assert_eq!(cache.insert(String::from("small"), 4), None);
+        assert_eq!(cache.insert(String::from("small"), 5), Some(4));
+        assert_eq!(cache.get(&String::from("small")), Some(&5));
+        assert_eq!(cache.get(&String::from("big")), None);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn inserted_values_are_hits_for_get_or_insert_with() {
+        let mut cache = Cache::new();
+        cache.insert(3, String::from("three"));
+        let value = cache.get_or_insert_with(3, || panic!("3 was already cached"));
+        assert_eq!(value, "three");
+    }
+
+    #[test]
+    fn smallest_hands_back_the_first_of_equals() {
+        assert_eq!(smallest(3, 2), 2);
+        assert_eq!(smallest("b", "a"), "a");
+        let (one, two) = (Version { major: 1, minor: 2 }, Version { major: 1, minor: 2 });
+        assert!(std::ptr::eq(smallest_ref(&one, &two), &one));
+        let (newer, older) = (Version { major: 2, minor: 0 }, Version { major: 1, minor: 9 });
+        assert_eq!(smallest(newer, older).major, 1);
+    }
+
+    #[test]
+    fn largest_of_a_slice() {
+        assert_eq!(largest(&[34, 50, 25, 100, 65]), Some(&100));
+        assert_eq!(largest(&['y', 'm', 'a', 'q']), Some(&'y'));
+        assert_eq!(largest::<char>(&[]), None);
+        assert_eq!(largest(&[7]), Some(&7));
+        assert_eq!(largest(&[1.0, f64::NAN, 3.0]), Some(&3.0));
+        assert!(largest(&[f64::NAN, 3.0]).unwrap().is_nan());
+    }
+
+    //Only the number is compared, so two of these can tie while still being told apart.
+    #[derive(Debug)]
+    struct Keyed(i32, char);
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            self.0.partial_cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn largest_keeps_the_first_of_equal_largest() {
+        let items = [Keyed(1, 'a'), Keyed(5, 'b'), Keyed(3, 'c'), Keyed(5, 'd'), Keyed(5, 'e')];
+        let found = largest(&items).unwrap();
+        assert_eq!(found.1, 'b');
+        assert!(std::ptr::eq(found, &items[1]));
+    }
+
+    #[test]
+    fn sums_and_checked_sums() {
+        assert_eq!(sum(&[1, 2, 3]), 6);
+        assert_eq!(sum::<f64>(&[]), 0.0);
+        assert_eq!(checked_sum(&[100i8, 27]), Some(127));
+        assert_eq!(checked_sum(&[100i8, 28]), None);
+        assert_eq!(checked_sum::<i64>(&[]), Some(0));
+        assert_eq!(checked_sum(&[isize::MAX, -1, 1]), Some(isize::MAX));
+    }
+
+    #[test]
+    fn mean_does_not_overflow_on_big_values() {
+        assert_eq!(mean(&[]), None);
+        assert_eq!(mean(&[1.0, 2.0, 3.0, 4.0]), Some(2.5));
+        assert_eq!(mean(&[f64::MAX, f64::MAX]), Some(f64::MAX));
+        assert_eq!(mean_of(&[1u8, 2, 4, 5]), Some(3.0));
+        assert_eq!(mean_of::<i32>(&[]), None);
+    }
+
+    #[test]
+    fn binary_search_matches_std() {
+        let items = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+        for key in 0..9 {
+            let ours = binary_search_by_key(&items, key, |item| item.0);
+            let std = items.binary_search_by_key(&key, |item| item.0);
+            assert_eq!(ours.is_ok(), std.is_ok(), "key {key}");
+            match ours {
+                Ok(i) => assert_eq!(items[i].0, key),
+                Err(i) => assert_eq!(Err(i), std),
+            }
+        }
+        assert_eq!(binary_search_by_key(&[] as &[i32], 5, |&n| n), Err(0));
+    }
+
+    #[test]
+    fn insert_sorted_puts_equal_items_last() {
+        let mut v = Vec::new();
+        for item in [(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')] {
+            insert_sorted(&mut v, item);
+        }
+        assert_eq!(v, [(1, 'b'), (1, 'e'), (2, 'd'), (3, 'a'), (3, 'c')]);
+    }
+
+    #[test]
+    fn closures_as_generics() {
+        assert_eq!(apply_n_times(1, 10, |n| n * 2), 1024);
+        assert_eq!(apply_n_times(String::from("x"), 0, |s| s + "y"), "x");
+        assert_eq!(first_passing(&[1, 4, 6], |&n| n % 2 == 0), Some(&4));
+        assert_eq!(first_passing(&[1, 3], |&n| n > 5), None);
+    }
+
+    #[test]
+    fn rectangles_compare_by_area() {
+        let wide = Rectangle { height: 2, width: 6 };
+        let tall = Rectangle { height: 3, width: 4 };
+        assert_eq!(wide, tall);
+        assert!(!wide.is_square());
+        assert_eq!((wide.area(), wide.perimeter()), (12, 16));
+        assert!(Rectangle { height: 1.5, width: 2.0 } < Rectangle { height: 2.0, width: 2.0 });
+        let nan = Rectangle { height: f64::NAN, width: 1.0 };
+        assert_eq!(nan.partial_cmp(&Rectangle { height: 1.0, width: 1.0 }), None);
+        let rects = [wide, Rectangle { height: 5, width: 5 }, tall];
+        assert_eq!(rects.iter().max().map(|r| r.area()), Some(25));
+    }
+
+    #[test]
+    fn rectangles_with_a_zero_side() {
+        let flat = Rectangle { height: 3, width: 0 };
+        assert_eq!((flat.area(), flat.perimeter()), (0, 6));
+        assert!(!flat.is_square());
+        let thin = Rectangle { height: 0.0, width: 2.5 };
+        assert_eq!((thin.area(), thin.perimeter()), (0.0, 5.0));
+        let point = Rectangle { height: 0, width: 0 };
+        assert!(point.is_square());
+        assert_eq!((point.area(), point.perimeter()), (0, 0));
+        assert_eq!(flat, point);
+    }
+
+    #[test]
+    fn intervals_include_both_ends() {
+        let a = Interval::new(1, 5).unwrap();
+        let b = Interval::new(5, 9).unwrap();
+        assert!(a.contains(&1) && a.contains(&5) && !a.contains(&6));
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersect(&b), Some(Interval::new(5, 5).unwrap()));
+        assert_eq!(a.intersect(&Interval::new(6, 7).unwrap()), None);
+        assert_eq!((*a.lo(), *a.hi()), (1, 5));
+    }
+
+    #[test]
+    fn intervals_have_to_be_in_order() {
+        assert_eq!(Interval::new(2, 1), Err(IntervalError::Reversed));
+        assert_eq!(Interval::new(f64::NAN, 1.0), Err(IntervalError::Unordered));
+        assert!(Interval::new(3, 3).is_ok());
+        assert_eq!(IntervalError::Reversed.to_string(), "the low end is above the high end");
+    }
+
+    #[test]
+    fn primary_colors_keep_their_variant() {
+        let colors = [
+            PrimaryColors::Yellow("sun"),
+            PrimaryColors::Blue("sky"),
+            PrimaryColors::Red("fire"),
+        ];
+        let rgb = colors.each_ref().map(|color| color.to_rgb());
+        assert_eq!(rgb, [(255, 255, 0), (0, 0, 255), (255, 0, 0)]);
+        assert_eq!(colors.each_ref().map(|color| color.label()), ["yellow", "blue", "red"]);
+        let lengths = colors.map(|color| color.map(str::len));
+        assert_eq!(
+            lengths,
+            [PrimaryColors::Yellow(3), PrimaryColors::Blue(3), PrimaryColors::Red(4)]
+        );
+        assert_eq!(*lengths[2].payload(), 4);
+        assert_eq!(PrimaryColors::Blue(String::from("sky")).to_string(), "blue:sky");
+        assert_eq!(PrimaryColors::Red(0u8).to_rgb(), PrimaryColors::Red("any").to_rgb());
+    }
+
+    #[test]
+    fn colors_parse_back_from_their_display() {
+        let parse = |s: &str| s.parse::<PrimaryColors<String>>();
+        assert_eq!(parse("RED:fire"), Ok(PrimaryColors::Red(String::from("fire"))));
+        assert_eq!(parse("yellow:a:b"), Ok(PrimaryColors::Yellow(String::from("a:b"))));
+        assert_eq!(parse("green:leaf"), Err(ColorParseError::UnknownColor(String::from("green"))));
+        assert_eq!(parse("blue"), Err(ColorParseError::EmptyPayload));
+        assert_eq!(parse("blue:"), Err(ColorParseError::EmptyPayload));
+        let red = PrimaryColors::Red(String::from("fire"));
+        assert_eq!(parse(&red.to_string()), Ok(red));
+    }
+
+    #[test]
+    fn mixing_ignores_the_payloads() {
+        use PrimaryColors::{Blue, Red, Yellow};
+        use SecondaryColors::{Green, Orange, Purple};
+        let colors = [Yellow(1), Blue(2), Red(3)];
+        let expected = [
+            [None, Some(Green), Some(Orange)],
+            [Some(Green), None, Some(Purple)],
+            [Some(Orange), Some(Purple), None],
+        ];
+        for (a, row) in colors.iter().zip(expected) {
+            for (b, mixed) in colors.iter().zip(row) {
+                assert_eq!(mix(a, b), mixed, "{a} and {b}");
+            }
+        }
+        assert_eq!(mix(&Yellow(1), &Blue("b")), Some(Green));
+        assert_eq!(mix(&Red(()), &Red(5)), None);
+        assert_eq!(mix(&Red(1), &Red(2)), None);
+        let labels = [Green, Orange, Purple].map(|color| color.to_string());
+        assert_eq!(labels, ["green", "orange", "purple"]);
+    }
+
+    #[test]
+    fn colors_cycle_forever() {
+        assert_eq!(nth_color(0), PrimaryColor::Yellow);
+        assert_eq!(nth_color(3), PrimaryColor::Yellow);
+        assert_eq!(nth_color(100), PrimaryColor::Blue);
+        assert_eq!(nth_color(usize::MAX), PrimaryColor::ALL[usize::MAX % 3]);
+        let seven: Vec<String> = ColorCycle::new().take(7).map(|color| color.to_string()).collect();
+        assert_eq!(seven, ["yellow", "blue", "red", "yellow", "blue", "red", "yellow"]);
+        assert!(ColorCycle::take_n(7).eq(ColorCycle::new().take(7)));
+        assert_eq!(ColorCycle::take_n(0).count(), 0);
+        assert_eq!(ColorCycle::default().nth(100), Some(nth_color(100)));
+    }
+
+    #[test]
+    fn triangle_types_can_change() {
+        let triangle = Triangle { base: 4isize, height: 6isize };
+        assert_eq!(triangle.area(), 12);
+        let swapped: Triangle<f64, i32> = Triangle { base: 3i32, height: 4.5f64 }.swap();
+        assert_eq!((*swapped.base(), *swapped.height()), (4.5, 3));
+        let relabeled = Triangle { base: 2.5, height: 3 }.with_height('h');
+        assert_eq!((*relabeled.base(), *relabeled.height()), (2.5, 'h'));
+    }
+
+    #[test]
+    fn points_mix_up_their_types() {
+        let mixed = Point { x: 5, y: 10.4 }.mixup(Point { x: "hello", y: 'c' });
+        assert_eq!((*mixed.x(), mixed.y), (5, 'c'));
+        assert_eq!(mixed.to_string(), "(5, c)");
+        assert_eq!(Point { x: 3.0f32, y: 4.0f32 }.distance_from_origin(), 5.0);
+    }
+
+    #[test]
+    fn stack_is_last_in_first_out() {
+        let mut stack = Stack::default();
+        assert!(stack.is_empty() && stack.pop().is_none());
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert_eq!((stack.peek(), Container::get(&stack, 0)), (Some(&3), Some(&3)));
+        assert_eq!(Container::get(&stack, 2), Some(&1));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.len(), 2);
+        let mut out: Vec<String> = Vec::new();
+        stack.print_all(&mut out);
+        assert_eq!(out, ["2", "1"]);
+    }
+}
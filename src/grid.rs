@@ -0,0 +1,151 @@
+use crate::output::Output;
+use crate::spatial::{Rect, Spatial};
+use crate::traits::{Square, Triangle};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+pub enum PlacementError {
+    OutOfBounds(Rect),
+    Overlaps(usize),
+}
+
+impl Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PlacementError::OutOfBounds(rect) => write!(
+                f,
+                "a {} by {} box at ({}, {}) doesn't fit on the grid",
+                rect.width, rect.height, rect.x, rect.y
+            ),
+            PlacementError::Overlaps(id) => write!(f, "it would overlap shape {id}"),
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
+
+//A board that shapes get laid out on by their bounding boxes. The grid runs from (0, 0) to
+// (width, height) and only remembers the boxes along with an id for whatever was placed there.
+pub struct Grid {
+    width: isize,
+    height: isize,
+    placed: Vec<(Rect, usize)>,
+}
+
+impl Grid {
+    pub fn new(width: isize, height: isize) -> Self {
+        Grid { width, height, placed: Vec::new() }
+    }
+
+    //The box is moved so that its own origin ends up at `at`. Reaching all the way to the far
+    // edge is fine, and so is sharing an edge with a shape that is already there.
+    pub fn place(
+        &mut self,
+        shape_id: usize,
+        bbox: Rect,
+        at: (isize, isize),
+    ) -> Result<(), PlacementError> {
+        let rect = bbox.moved_by(at.0, at.1);
+        let inside = rect.x >= 0
+            && rect.y >= 0
+            && rect.x + rect.width <= self.width
+            && rect.y + rect.height <= self.height;
+        if !inside {
+            return Err(PlacementError::OutOfBounds(rect));
+        }
+        if let Some((_, id)) = self.placed.iter().find(|(other, _)| rect.overlaps(other)) {
+            return Err(PlacementError::Overlaps(*id));
+        }
+        self.placed.push((rect, shape_id));
+        Ok(())
+    }
+
+    //How much of the grid is covered by bounding boxes, from 0.0 to 1.0. Nothing overlaps, so
+    // the areas can just be added up. A grid with no room at all counts as empty.
+    pub fn occupancy(&self) -> f64 {
+        let total = self.width * self.height;
+        if total == 0 {
+            return 0.0;
+        }
+        let filled: isize = self.placed.iter().map(|(rect, _)| rect.area()).sum();
+        filled as f64 / total as f64
+    }
+
+    pub fn placed(&self) -> &[(Rect, usize)] {
+        &self.placed
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut grid = Grid::new(20, 10);
+    out.line(format!("empty grid occupancy: {}", grid.occupancy()));
+
+    let square = Square { height: 10 };
+    let triangle = Triangle { base: 5, height: 10 };
+    let small = Square { height: 5 };
+    let attempts = [
+        (0, square.bounding_box(), (0, 0)),
+        //Touches the right edge of the square.
+        (1, triangle.bounding_box(), (10, 0)),
+        //Fits exactly into the top right corner of the grid.
+        (2, small.bounding_box(), (15, 5)),
+        (3, triangle.bounding_box(), (5, 0)),
+        (4, small.bounding_box(), (17, 0)),
+    ];
+    for (id, bbox, at) in attempts {
+        match grid.place(id, bbox, at) {
+            Ok(()) => out.line(format!("placed shape {id} at {:?}", at)),
+            Err(e) => out.line(format!("couldn't place shape {id} at {:?}: {e}", at)),
+        }
+    }
+    out.line(format!("{} shapes placed, occupancy: {}", grid.placed().len(), grid.occupancy()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(side: isize) -> Rect {
+        Square { height: side }.bounding_box()
+    }
+
+    #[test]
+    fn touching_shapes_and_edges_are_allowed() {
+        let mut grid = Grid::new(20, 10);
+        assert_eq!(grid.place(0, square(10), (0, 0)), Ok(()));
+        assert_eq!(grid.place(1, Triangle { base: 5, height: 10 }.bounding_box(), (10, 0)), Ok(()));
+        assert_eq!(grid.place(2, square(5), (15, 5)), Ok(()));
+        let ids: Vec<usize> = grid.placed().iter().map(|(_, id)| *id).collect();
+        assert_eq!(ids, [0, 1, 2]);
+    }
+
+    #[test]
+    fn overlapping_names_the_shape_in_the_way() {
+        let mut grid = Grid::new(20, 10);
+        grid.place(7, square(10), (0, 0)).unwrap();
+        assert_eq!(grid.place(8, square(5), (5, 5)), Err(PlacementError::Overlaps(7)));
+        assert_eq!(grid.placed().len(), 1);
+    }
+
+    #[test]
+    fn out_of_bounds_reports_where_the_box_ended_up() {
+        let mut grid = Grid::new(20, 10);
+        let err = grid.place(0, square(5), (17, 0)).unwrap_err();
+        assert_eq!(err, PlacementError::OutOfBounds(Rect { x: 17, y: 0, width: 5, height: 5 }));
+        assert_eq!(err.to_string(), "a 5 by 5 box at (17, 0) doesn't fit on the grid");
+        assert!(grid.place(0, square(1), (-1, 0)).is_err());
+        assert!(grid.placed().is_empty());
+    }
+
+    #[test]
+    fn occupancy_goes_from_zero_to_one() {
+        let mut grid = Grid::new(10, 10);
+        assert_eq!(grid.occupancy(), 0.0);
+        grid.place(0, square(5), (0, 0)).unwrap();
+        assert_eq!(grid.occupancy(), 0.25);
+        grid.place(1, Rect { x: 0, y: 0, width: 10, height: 5 }, (0, 5)).unwrap();
+        grid.place(2, square(5), (5, 0)).unwrap();
+        assert_eq!(grid.occupancy(), 1.0);
+        assert_eq!(Grid::new(0, 5).occupancy(), 0.0);
+    }
+}
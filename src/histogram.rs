@@ -0,0 +1,100 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+use std::fmt::Display;
+
+//Counts values into buckets. Each boundary is the upper end of its bucket and isn't included in
+// it, so a value exactly on a boundary goes in the next bucket up. Anything at or past the last
+// boundary goes in the overflow bucket. The boundaries are expected to be given smallest first.
+pub struct Histogram<T: PartialOrd + Copy> {
+    buckets: Vec<(T, usize)>,
+    overflow: usize,
+}
+
+impl<T: PartialOrd + Copy> Histogram<T> {
+    pub fn new(boundaries: &[T]) -> Self {
+        Histogram {
+            buckets: boundaries.iter().map(|&boundary| (boundary, 0)).collect(),
+            overflow: 0,
+        }
+    }
+
+    pub fn add(&mut self, value: T) {
+        match self.buckets.iter_mut().find(|(boundary, _)| value < *boundary) {
+            Some((_, count)) => *count += 1,
+            None => self.overflow += 1,
+        }
+    }
+
+    pub fn counts(&self) -> &[(T, usize)] {
+        &self.buckets
+    }
+
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+}
+
+//One `#` per value. The overflow bucket is always drawn, even when it is empty.
+impl<T: PartialOrd + Copy + Display> Histogram<T> {
+    pub fn render(&self) -> String {
+        let mut lines: Vec<String> = self
+            .buckets
+            .iter()
+            .map(|(boundary, count)| format!("< {boundary}: {}", "#".repeat(*count)))
+            .collect();
+        let last = match self.buckets.last() {
+            Some((boundary, _)) => format!(">= {boundary}"),
+            None => String::from("all"),
+        };
+        lines.push(format!("{last}: {}", "#".repeat(self.overflow)));
+        lines.join("\n")
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+    for i in 1..=8 {
+        shapes.push(Box::new(Square { height: i }));
+        shapes.push(Box::new(Triangle { base: i, height: 2 * i }));
+    }
+
+    let mut histogram = Histogram::new(&[10, 25, 50]);
+    for shape in &shapes {
+        histogram.add(shape.area());
+    }
+    out.line(format!("counts: {:?} overflow: {}", histogram.counts(), histogram.overflow()));
+    out.line(histogram.render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled<T: PartialOrd + Copy>(boundaries: &[T], values: &[T]) -> Histogram<T> {
+        let mut histogram = Histogram::new(boundaries);
+        values.iter().for_each(|&value| histogram.add(value));
+        histogram
+    }
+
+    #[test]
+    fn boundaries_belong_to_the_next_bucket_up() {
+        let histogram = filled(&[10, 20], &[0, 9, 10, 19, 20, 100]);
+        assert_eq!(histogram.counts(), [(10, 2), (20, 2)]);
+        assert_eq!(histogram.overflow(), 2);
+    }
+
+    #[test]
+    fn no_boundaries_puts_everything_in_overflow() {
+        let histogram = filled(&[], &[1.5, -3.0]);
+        assert!(histogram.counts().is_empty());
+        assert_eq!(histogram.overflow(), 2);
+        assert_eq!(histogram.render(), "all: ##");
+    }
+
+    #[test]
+    fn render_draws_one_mark_per_value_and_an_empty_overflow() {
+        let histogram = filled(&[10, 25], &[1, 2, 3, 12]);
+        assert_eq!(histogram.render(), "< 10: ###\n< 25: #\n>= 25: ");
+        assert_eq!(Histogram::<i32>::new(&[]).render(), "all: ");
+    }
+}
@@ -0,0 +1,157 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+//An id that remembers what type of thing it is for, even though all it holds is a number.
+// PhantomData<T> takes up no space, it is only there so the T gets used somewhere. Without it
+// the compiler complains that T is never used.
+pub struct Id<T> {
+    value: u64,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Id<T> {
+    pub fn new(value: u64) -> Self {
+        Id { value, _marker: PhantomData }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+//#[derive(Clone, Copy, PartialEq)] would add a `T: Clone` (and so on) bound to every one of
+// these, because derive doesn't look at how T is actually used. That would mean an Id<T> could
+// only be copied when T could be, and Square and Triangle aren't Copy. Only the number needs to
+// be copied or compared, so the impls are written out by hand without any bounds on T.
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> Debug for Id<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Id({})", self.value)
+    }
+}
+
+//Only hands out ids for its own T, so looking something up with an id for the wrong type of
+// shape is a compile error instead of a wrong answer. The ids count up from 0 and are unique
+// within one store. Two stores of the same type will hand out the same numbers though.
+pub struct TypedStore<T> {
+    items: Vec<T>,
+}
+
+impl<T> TypedStore<T> {
+    pub fn new() -> Self {
+        TypedStore { items: Vec::new() }
+    }
+
+    pub fn insert(&mut self, item: T) -> Id<T> {
+        self.items.push(item);
+        Id::new(self.items.len() as u64 - 1)
+    }
+
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        self.items.get(id.value as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> Default for TypedStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut squares = TypedStore::new();
+    let mut triangles = TypedStore::new();
+    let small = squares.insert(Square { height: 2 });
+    let big = squares.insert(Square { height: 10 });
+    let triangle = triangles.insert(Triangle { base: 5, height: 10 });
+
+    out.line(format!("square ids: {:?} {:?} triangle id: {:?}", small, big, triangle));
+    out.line(format!("big square area: {:?}", squares.get(big).map(|s| s.area())));
+    out.line(format!("triangle area: {:?}", triangles.get(triangle).map(|t| t.area())));
+    out.line(format!("square id 7: {:?}", squares.get(Id::new(7)).map(|s| s.area())));
+    //The line below won't compile, an Id<Triangle> can't be used to look up a square.
+    // squares.get(triangle);
+
+    let copy = big;
+    let ids: HashSet<Id<Square>> = [small, big, copy].into_iter().collect();
+    out.line(format!(
+        "copy equals the original? {} distinct ids in the set: {}",
+        copy == big,
+        ids.len()
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_count_up_from_zero_in_each_store() {
+        let mut squares = TypedStore::default();
+        assert!(squares.is_empty());
+        let first = squares.insert(Square { height: 2 });
+        let second = squares.insert(Square { height: 3 });
+        assert_eq!((first.value(), second.value()), (0, 1));
+        assert_eq!(squares.len(), 2);
+        let mut triangles = TypedStore::new();
+        assert_eq!(triangles.insert(Triangle { base: 1, height: 1 }).value(), 0);
+    }
+
+    #[test]
+    fn get_finds_the_item_or_nothing() {
+        let mut squares = TypedStore::new();
+        let id = squares.insert(Square { height: 4 });
+        assert_eq!(squares.get(id).map(|square| square.area()), Some(16));
+        assert!(squares.get(Id::new(1)).is_none());
+    }
+
+    //Square isn't Copy or Hash, so none of this would compile with derived impls.
+    #[test]
+    fn ids_copy_compare_and_hash_without_bounds_on_t() {
+        let id: Id<Square> = Id::new(3);
+        let copy = id;
+        assert_eq!(id, copy);
+        assert_ne!(id, Id::new(4));
+        let set: HashSet<Id<Square>> = [id, copy, Id::new(4)].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(format!("{id:?}"), "Id(3)");
+    }
+
+    #[test]
+    fn an_id_takes_no_more_room_than_its_number() {
+        assert_eq!(std::mem::size_of::<Id<Square>>(), std::mem::size_of::<u64>());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::leaderboard::Leaderboard;
+use crate::output::Output;
+use crate::registry::ShapeRegistry;
+use crate::traits::{Square, Triangle};
+
+//A symbol is just the position of a string in the interner. Copying one around is as cheap as
+// copying a number, and two symbols from the same interner are equal exactly when their
+// strings are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sym(usize);
+
+//Keeps one copy of every string it has been given. Strings are never removed, so a Sym stays
+// valid for as long as the interner is around.
+pub struct Interner {
+    storage: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Interner { storage: Vec::new() }
+    }
+
+    //Looking for the string is a linear search, which is fine for the handful of names used
+    // here. The empty string is interned like any other.
+    pub fn intern(&mut self, s: &str) -> Sym {
+        match self.storage.iter().position(|stored| stored == s) {
+            Some(i) => Sym(i),
+            None => {
+                self.storage.push(s.to_string());
+                Sym(self.storage.len() - 1)
+            }
+        }
+    }
+
+    //Panics for a Sym that came from a different interner and is past the end of this one.
+    pub fn resolve(&self, sym: Sym) -> &str {
+        &self.storage[sym.0]
+    }
+
+    //Rule #3 would already give the output the lifetime of &self, so the elided version means
+    // the same thing. 'a is written out to make that visible: the strings live in the interner,
+    // not in syms, so the slice of symbols can be dropped as soon as this returns.
+    pub fn resolve_many<'a>(&'a self, syms: &[Sym]) -> Vec<&'a str> {
+        syms.iter().map(|&sym| self.resolve(sym)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut names = Interner::new();
+    let square = names.intern("square");
+    let triangle = names.intern("triangle");
+    let again = names.intern("square");
+    let empty = names.intern("");
+    out.line(format!(
+        "square: {:?} triangle: {:?} square again: {:?} empty: {:?} ({} stored)",
+        square,
+        triangle,
+        again,
+        empty,
+        names.len()
+    ));
+
+    //The symbols are made from a temporary Vec, but the strings still borrow from names.
+    let resolved = names.resolve_many(&[triangle, square, empty]);
+    out.line(format!("resolved: {:?}", resolved));
+
+    let mut registry = ShapeRegistry::new();
+    registry.register(names.resolve(square), Box::new(Square { height: 10 }));
+    registry.register(names.resolve(triangle), Box::new(Triangle { base: 5, height: 10 }));
+    let mut board = Leaderboard::new();
+    for sym in [square, triangle] {
+        let name = names.resolve(sym);
+        out.line(format!("area of {name}: {:?}", registry.area_of(name)));
+        if let Some(shape) = registry.get(name) {
+            board.insert(name, shape.clone_box());
+        }
+    }
+    out.line(format!("rank of triangle: {:?}", board.rank_of(names.resolve(triangle))));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_again_gives_the_same_sym() {
+        let mut names = Interner::new();
+        let square = names.intern("square");
+        let triangle = names.intern("triangle");
+        assert_ne!(square, triangle);
+        assert_eq!(names.intern("square"), square);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.resolve(square), "square");
+    }
+
+    #[test]
+    fn empty_string_is_interned_like_any_other() {
+        let mut names = Interner::new();
+        assert!(names.is_empty());
+        let empty = names.intern("");
+        assert_eq!(names.intern(""), empty);
+        assert_eq!(names.resolve(empty), "");
+        assert_eq!(names.len(), 1);
+    }
+
+    #[test]
+    fn earlier_syms_still_resolve_after_many_inserts() {
+        let mut names = Interner::new();
+        let first = names.intern("first");
+        let syms: Vec<Sym> = (0..1000).map(|i| names.intern(&i.to_string())).collect();
+        assert_eq!(names.resolve(first), "first");
+        assert_eq!(names.resolve(syms[999]), "999");
+        assert_eq!(names.len(), 1001);
+    }
+
+    #[test]
+    fn resolve_many_outlives_the_slice_of_syms() {
+        let mut names = Interner::new();
+        let a = names.intern("a");
+        let b = names.intern("b");
+        let resolved = {
+            let syms = Vec::from([b, a, b]);
+            names.resolve_many(&syms)
+        };
+        assert_eq!(resolved, ["b", "a", "b"]);
+        assert!(names.resolve_many(&[]).is_empty());
+    }
+}
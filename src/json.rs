@@ -0,0 +1,346 @@
+use crate::output::Output;
+use crate::traits::{Shape, ShapeError, Square, Triangle};
+use std::fmt::Display;
+
+//Just enough JSON to write the shapes out and read them back in, without pulling in a crate.
+// Only flat objects holding strings and whole numbers are understood.
+pub trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+//Strings are the only thing that need escaping. Anything below a space has to be written as an
+// escape as well, the common ones get their short forms.
+pub fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> String {
+        format!("\"{}\"", escape_json(self))
+    }
+}
+
+impl ToJson for Square {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"type\":{},\"height\":{},\"area\":{}}}",
+            "square".to_json(),
+            self.height,
+            self.area()
+        )
+    }
+}
+
+impl ToJson for Triangle {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"type\":{},\"base\":{},\"height\":{},\"area\":{}}}",
+            "triangle".to_json(),
+            self.base,
+            self.height,
+            self.area()
+        )
+    }
+}
+
+//`Box<dyn Shape + ToJson>` isn't allowed, a trait object can only have one trait that isn't an
+// auto trait like Send. Combining the two into one trait gets around that, and the blanket impl
+// means nothing has to implement JsonShape by hand.
+pub trait JsonShape: Shape + ToJson {}
+
+impl<T: Shape + ToJson> JsonShape for T {}
+
+impl<T: ToJson + ?Sized> ToJson for Box<T> {
+    fn to_json(&self) -> String {
+        self.as_ref().to_json()
+    }
+}
+
+//Covers a Vec of boxed shapes too, since a Vec derefs to a slice.
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> String {
+        let items: Vec<String> = self.iter().map(|item| item.to_json()).collect();
+        format!("[{}]", items.join(","))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum JsonError {
+    UnexpectedEnd,
+    Expected(char, char),
+    InvalidNumber(String),
+    TrailingCharacters(String),
+    MissingField(&'static str),
+    NotANumber(&'static str),
+    WrongType { expected: &'static str, found: String },
+    Invalid(ShapeError),
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            JsonError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            JsonError::Expected(expected, found) => {
+                write!(f, "expected '{expected}' but found '{found}'")
+            }
+            JsonError::InvalidNumber(value) => write!(f, "'{value}' is not a number"),
+            JsonError::TrailingCharacters(rest) => {
+                write!(f, "unexpected '{rest}' after the object")
+            }
+            JsonError::MissingField(field) => write!(f, "missing field '{field}'"),
+            JsonError::NotANumber(field) => write!(f, "field '{field}' should be a number"),
+            JsonError::WrongType { expected, found } => {
+                write!(f, "expected type '{expected}' but found '{found}'")
+            }
+            JsonError::Invalid(e) => write!(f, "invalid shape: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+impl From<ShapeError> for JsonError {
+    fn from(e: ShapeError) -> Self {
+        JsonError::Invalid(e)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum JsonValue {
+    Text(String),
+    Number(isize),
+}
+
+//Each of the parsing helpers takes the input left to parse and hands back whatever it parsed
+// along with the input that is left after it.
+fn expect(s: &str, c: char) -> Result<&str, JsonError> {
+    match s.chars().next() {
+        Some(found) if found == c => Ok(&s[c.len_utf8()..]),
+        Some(found) => Err(JsonError::Expected(c, found)),
+        None => Err(JsonError::UnexpectedEnd),
+    }
+}
+
+fn parse_string(s: &str) -> Result<(String, &str), JsonError> {
+    let s = expect(s, '"')?;
+    let mut text = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((text, &s[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some('t') => text.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or(JsonError::InvalidNumber(hex))?;
+                    text.push(c);
+                }
+                Some(c) => text.push(c),
+                None => return Err(JsonError::UnexpectedEnd),
+            },
+            c => text.push(c),
+        }
+    }
+    Err(JsonError::UnexpectedEnd)
+}
+
+fn parse_value(s: &str) -> Result<(JsonValue, &str), JsonError> {
+    if s.starts_with('"') {
+        let (text, rest) = parse_string(s)?;
+        return Ok((JsonValue::Text(text), rest));
+    }
+    let end = s.find([',', '}', ' ', '\n', '\t', '\r']).unwrap_or(s.len());
+    let (number, rest) = s.split_at(end);
+    if number.is_empty() {
+        return Err(s.chars().next().map_or(JsonError::UnexpectedEnd, |found| {
+            JsonError::InvalidNumber(found.to_string())
+        }));
+    }
+    let number = number.parse().map_err(|_| JsonError::InvalidNumber(number.to_string()))?;
+    Ok((JsonValue::Number(number), rest))
+}
+
+//A flat object like `{"type":"square","height":10}`. Only whitespace is allowed after the
+// closing brace.
+fn parse_object(s: &str) -> Result<Vec<(String, JsonValue)>, JsonError> {
+    let mut rest = expect(s.trim_start(), '{')?.trim_start();
+    let mut fields = Vec::new();
+    if let Some(after) = rest.strip_prefix('}') {
+        rest = after;
+    } else {
+        loop {
+            let (key, after) = parse_string(rest.trim_start())?;
+            let after = expect(after.trim_start(), ':')?;
+            let (value, after) = parse_value(after.trim_start())?;
+            fields.push((key, value));
+            let after = after.trim_start();
+            if let Some(after) = after.strip_prefix(',') {
+                rest = after;
+                continue;
+            }
+            rest = expect(after, '}')?;
+            break;
+        }
+    }
+    let rest = rest.trim();
+    if !rest.is_empty() {
+        return Err(JsonError::TrailingCharacters(rest.to_string()));
+    }
+    Ok(fields)
+}
+
+fn field<'a>(
+    fields: &'a [(String, JsonValue)],
+    name: &'static str,
+) -> Result<&'a JsonValue, JsonError> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+        .ok_or(JsonError::MissingField(name))
+}
+
+fn number_field(fields: &[(String, JsonValue)], name: &'static str) -> Result<isize, JsonError> {
+    match field(fields, name)? {
+        JsonValue::Number(number) => Ok(*number),
+        JsonValue::Text(_) => Err(JsonError::NotANumber(name)),
+    }
+}
+
+fn check_type(fields: &[(String, JsonValue)], expected: &'static str) -> Result<(), JsonError> {
+    match field(fields, "type")? {
+        JsonValue::Text(found) if found == expected => Ok(()),
+        JsonValue::Text(found) => Err(JsonError::WrongType { expected, found: found.clone() }),
+        JsonValue::Number(found) => {
+            Err(JsonError::WrongType { expected, found: found.to_string() })
+        }
+    }
+}
+
+//The area that to_json writes out is worked out from the other fields, so it is ignored when
+// reading a shape back in. Any other extra fields are ignored as well.
+impl Square {
+    pub fn from_json(s: &str) -> Result<Square, JsonError> {
+        let fields = parse_object(s)?;
+        check_type(&fields, "square")?;
+        Ok(Square::new(number_field(&fields, "height")?)?)
+    }
+}
+
+impl Triangle {
+    pub fn from_json(s: &str) -> Result<Triangle, JsonError> {
+        let fields = parse_object(s)?;
+        check_type(&fields, "triangle")?;
+        Ok(Triangle::new(number_field(&fields, "base")?, number_field(&fields, "height")?)?)
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let shapes: Vec<Box<dyn JsonShape>> =
+        vec![Box::new(Square { height: 10 }), Box::new(Triangle { base: 5, height: 10 })];
+    out.line(format!("shapes as json: {}", shapes.to_json()));
+    out.line(format!("escaped: {}", "a \"quoted\"\tname".to_json()));
+
+    let square_json = Square { height: 4 }.to_json();
+    match Square::from_json(&square_json) {
+        Ok(square) => out.line(format!("{square_json} reads back as {square}")),
+        Err(e) => out.line(format!("{square_json} did not read back: {e}")),
+    }
+
+    let inputs = [
+        r#"{ "type": "triangle", "base": 3, "height": 4 }"#,
+        r#"{"type":"triangle","base":3}"#,
+        r#"{"type":"square","base":3,"height":4}"#,
+        r#"{"type":"triangle","base":"three","height":4}"#,
+        r#"{"type":"triangle","base":3,"height":4} extra"#,
+    ];
+    for input in inputs {
+        match Triangle::from_json(input) {
+            Ok(triangle) => out.line(format!("read {triangle}")),
+            Err(e) => out.line(format!("could not read {input}: {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_quotes_backslashes_and_control_characters() {
+        assert_eq!(escape_json("plain"), "plain");
+        assert_eq!(escape_json("a \"b\" \\ c"), "a \\\"b\\\" \\\\ c");
+        assert_eq!(escape_json("\n\r\t\u{1}"), "\\n\\r\\t\\u0001");
+        assert_eq!("é".to_json(), "\"é\"");
+    }
+
+    #[test]
+    fn shapes_and_lists_write_out_their_area() {
+        assert_eq!(Square { height: 3 }.to_json(), r#"{"type":"square","height":3,"area":9}"#);
+        let shapes: Vec<Box<dyn JsonShape>> =
+            Vec::from([Box::new(Square { height: 1 }) as Box<dyn JsonShape>]);
+        assert_eq!(shapes.to_json(), r#"[{"type":"square","height":1,"area":1}]"#);
+        let none: [Square; 0] = [];
+        assert_eq!(none.to_json(), "[]");
+    }
+
+    #[test]
+    fn shapes_read_back_what_they_wrote() {
+        let square = Square { height: 4 };
+        assert_eq!(Square::from_json(&square.to_json()), Ok(square));
+        let triangle = Triangle { base: 5, height: 10 };
+        assert_eq!(Triangle::from_json(&triangle.to_json()), Ok(triangle));
+    }
+
+    #[test]
+    fn whitespace_escapes_and_extra_fields_are_fine() {
+        let input = r#" {
+            "name" : "a \"b\" \u00e9", "type": "square",
+            "height": 2 } "#;
+        assert_eq!(Square::from_json(input), Ok(Square { height: 2 }));
+    }
+
+    #[test]
+    fn every_kind_of_error() {
+        let cases = [
+            (r#"{"type":"triangle","base":3}"#, JsonError::MissingField("height")),
+            (r#"{"type":"square","height":4}"#, JsonError::WrongType {
+                expected: "triangle",
+                found: String::from("square"),
+            }),
+            (r#"{"type":"triangle","base":"three","height":4}"#, JsonError::NotANumber("base")),
+            (r#"{"type":"triangle","base":3,"height":4} extra"#, JsonError::TrailingCharacters(
+                String::from("extra"),
+            )),
+            (r#"{"type":"triangle","base":3x,"height":4}"#, JsonError::InvalidNumber(
+                String::from("3x"),
+            )),
+            (r#"["type"]"#, JsonError::Expected('{', '[')),
+            (r#"{"type" "triangle"}"#, JsonError::Expected(':', '"')),
+            (r#"{"type":"triangle"#, JsonError::UnexpectedEnd),
+            (r#"{"type":"triangle","base":0,"height":4}"#, JsonError::Invalid(
+                ShapeError::NonPositiveBase(0),
+            )),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Triangle::from_json(input), Err(expected), "{input}");
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+use std::cell::{Cell, RefCell};
+use std::ops::{Index, IndexMut};
+
+//Shapes ranked by area, biggest first. leaderboard[0] is always the biggest shape, even right
+// after one of them was changed through leaderboard[i].
+//
+//The entries never move around inside the Vec, instead order holds their positions in ranked
+// order. IndexMut can't re-sort straight away because the caller only changes the shape after
+// index_mut has returned, so it just marks the order as dirty. The next read sorts it again,
+// but reads only get &self, which is why order and dirty are in a RefCell and a Cell. Index
+// still hands out a plain reference because it points into entries, not into the RefCell.
+pub struct Leaderboard {
+    entries: Vec<(String, Box<dyn Shape>)>,
+    order: RefCell<Vec<usize>>,
+    dirty: Cell<bool>,
+}
+
+impl Leaderboard {
+    pub fn new() -> Self {
+        Leaderboard {
+            entries: Vec::new(),
+            order: RefCell::new(Vec::new()),
+            dirty: Cell::new(false),
+        }
+    }
+
+    //A name that is already on the board gets its shape replaced, and the old one is handed
+    // back, the same as ShapeRegistry::register.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        shape: Box<dyn Shape>,
+    ) -> Option<Box<dyn Shape>> {
+        let name = name.into();
+        let old = match self.entries.iter_mut().find(|(entry, _)| *entry == name) {
+            Some((_, existing)) => Some(std::mem::replace(existing, shape)),
+            None => {
+                self.entries.push((name, shape));
+                None
+            }
+        };
+        self.dirty.set(true);
+        self.sort();
+        old
+    }
+
+    //Shapes with the same area stay in the order they were inserted in, sort_by_key is stable.
+    fn sort(&self) {
+        if !self.dirty.get() {
+            return;
+        }
+        let mut order = self.order.borrow_mut();
+        *order = (0..self.entries.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.entries[i].1.area()));
+        self.dirty.set(false);
+    }
+
+    //Where the entry at a rank actually is in entries. The RefCell borrow ends before this
+    // returns, so nothing ever holds on to it while sort might need it.
+    fn position(&self, rank: usize) -> Option<usize> {
+        self.sort();
+        self.order.borrow().get(rank).copied()
+    }
+
+    pub fn get(&self, rank: usize) -> Option<&(String, Box<dyn Shape>)> {
+        self.position(rank).map(|i| &self.entries[i])
+    }
+
+    pub fn rank_of(&self, name: &str) -> Option<usize> {
+        self.sort();
+        self.order.borrow().iter().position(|&i| self.entries[i].0 == name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//Like indexing a Vec, asking for a rank past the end panics. get is the version that doesn't.
+impl Index<usize> for Leaderboard {
+    type Output = (String, Box<dyn Shape>);
+
+    fn index(&self, rank: usize) -> &Self::Output {
+        match self.position(rank) {
+            Some(i) => &self.entries[i],
+            None => panic!("rank {rank} is out of bounds for {} entries", self.entries.len()),
+        }
+    }
+}
+
+impl IndexMut<usize> for Leaderboard {
+    fn index_mut(&mut self, rank: usize) -> &mut Self::Output {
+        let len = self.entries.len();
+        match self.position(rank) {
+            Some(i) => {
+                self.dirty.set(true);
+                &mut self.entries[i]
+            }
+            None => panic!("rank {rank} is out of bounds for {len} entries"),
+        }
+    }
+}
+
+fn show(board: &Leaderboard, out: &mut dyn Output) {
+    let ranked: Vec<String> = (0..board.len())
+        .map(|rank| format!("{} ({})", board[rank].0, board[rank].1.area()))
+        .collect();
+    out.line(format!("leaderboard: {}", ranked.join(", ")));
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut board = Leaderboard::new();
+    board.insert("small square", Box::new(Square { height: 2 }));
+    board.insert("triangle", Box::new(Triangle { base: 5, height: 10 }));
+    board.insert("big square", Box::new(Square { height: 10 }));
+    show(&board, out);
+
+    //Swapping out the biggest shape for a tiny one moves it to the bottom.
+    board[0].1 = Box::new(Square { height: 1 });
+    out.line(format!("big square is now rank {:?}", board.rank_of("big square")));
+    show(&board, out);
+
+    let old = board.insert("triangle", Box::new(Triangle { base: 20, height: 20 }));
+    out.line(format!("replaced a triangle of area {:?}", old.map(|shape| shape.area())));
+    show(&board, out);
+    out.line(format!("rank 3: {:?}", board.get(3).map(|(name, _)| name)));
+    out.line(format!("rank of circle: {:?}", board.rank_of("circle")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Leaderboard {
+        let mut board = Leaderboard::default();
+        board.insert("small square", Box::new(Square { height: 2 }));
+        board.insert("triangle", Box::new(Triangle { base: 5, height: 10 }));
+        board.insert("big square", Box::new(Square { height: 10 }));
+        board
+    }
+
+    fn names(board: &Leaderboard) -> Vec<&str> {
+        (0..board.len()).map(|rank| board[rank].0.as_str()).collect()
+    }
+
+    #[test]
+    fn ranked_biggest_first() {
+        let board = board();
+        assert_eq!(names(&board), ["big square", "triangle", "small square"]);
+        assert_eq!(board.rank_of("triangle"), Some(1));
+        assert_eq!(board.rank_of("circle"), None);
+        assert!(board.get(3).is_none());
+    }
+
+    #[test]
+    fn ties_keep_the_order_they_were_inserted_in() {
+        let mut board = Leaderboard::new();
+        board.insert("first", Box::new(Square { height: 2 }));
+        board.insert("second", Box::new(Triangle { base: 2, height: 4 }));
+        board.insert("third", Box::new(Square { height: 2 }));
+        assert_eq!(names(&board), ["first", "second", "third"]);
+    }
+
+    #[test]
+    fn changing_a_shape_through_index_mut_re_ranks_it() {
+        let mut board = board();
+        board[0].1 = Box::new(Square { height: 1 });
+        assert_eq!(board.rank_of("big square"), Some(2));
+        assert_eq!(names(&board), ["triangle", "small square", "big square"]);
+    }
+
+    #[test]
+    fn inserting_an_existing_name_replaces_its_shape() {
+        let mut board = board();
+        let old = board.insert("small square", Box::new(Square { height: 20 }));
+        assert_eq!(old.map(|shape| shape.area()), Some(4));
+        assert_eq!(board.len(), 3);
+        assert_eq!(board.rank_of("small square"), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "rank 3 is out of bounds for 3 entries")]
+    fn indexing_past_the_end_panics() {
+        let _ = &board()[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "rank 0 is out of bounds for 0 entries")]
+    fn index_mut_on_an_empty_board_panics() {
+        let mut board = Leaderboard::new();
+        board[0].1 = Box::new(Square { height: 1 });
+    }
+}
@@ -0,0 +1,166 @@
+//The generics, traits and lifetimes modules each cover one section of chapter 10. The run_demo
+// function in each one prints out the examples that were worked through for that section. The
+// rest of the modules are bigger examples that grew out of those sections.
+pub mod arena;
+pub mod builder;
+pub mod cached;
+pub mod cli;
+pub mod collection;
+pub mod composite;
+pub mod diff;
+pub mod dispatch;
+pub mod error;
+pub mod export;
+pub mod expr;
+pub mod generic_shapes;
+pub mod generics;
+pub mod grid;
+pub mod histogram;
+pub mod id;
+pub mod interner;
+pub mod json;
+pub mod leaderboard;
+pub mod lifetimes;
+pub mod observer;
+pub mod output;
+pub mod parse;
+pub mod polygon;
+pub mod ppm;
+pub mod quiz;
+pub mod registry;
+pub mod repl;
+pub mod report;
+pub mod render;
+pub mod rng;
+pub mod spatial;
+pub mod table;
+pub mod term;
+pub mod timing;
+pub mod traits;
+pub mod tree;
+pub mod triangle;
+pub mod units;
+
+use error::ChapterError;
+use output::Output;
+use report::SectionReport;
+use term::{Color, Term};
+
+pub const SECTIONS: [&str; 3] = ["generics", "traits", "lifetimes"];
+
+//Not a section of the book, but it can be run the same way with `cargo run -- bench`.
+pub const BENCH: &str = "bench";
+pub const BENCH_SIZE: usize = 1_000_000;
+
+//The settings that come from the command line rather than from which section is run. Default
+// is what the tests use, plain text and the full size bench.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunOptions {
+    pub term: Term,
+    pub bench_size: usize,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions { term: Term::plain(), bench_size: BENCH_SIZE }
+    }
+}
+
+//Runs a single section by name, or all of them when no name is given, and hands back the report
+// from every section that ran. An unknown name comes back as an error holding the usage message.
+pub fn run(
+    section: Option<&str>,
+    out: &mut dyn Output,
+    options: RunOptions,
+) -> Result<Vec<SectionReport>, ChapterError> {
+    let term = options.term;
+    let reports = match section {
+        None => SECTIONS.iter().map(|name| run_section(name, out, term)).collect(),
+        Some(name) if SECTIONS.contains(&name) => vec![run_section(name, out, term)],
+        Some(BENCH) => {
+            dispatch::bench(options.bench_size, out);
+            Vec::new()
+        }
+        Some(unknown) => {
+            let message = format!("unknown section '{unknown}'\n{}", cli::usage());
+            return Err(ChapterError::Usage(message));
+        }
+    };
+    Ok(reports)
+}
+
+//Prints a header and then the section itself, indented under it. Only ever called with a name
+// from SECTIONS. The section is collected into a String first and then passed on line by line.
+fn run_section(name: &str, out: &mut dyn Output, term: Term) -> SectionReport {
+    let mut text = String::new();
+    let report = {
+        let title = term.colorize(&format!("== {name} =="), Color::Blue);
+        let mut nested = output::section(&mut text, &title);
+        match name {
+            "generics" => generics::run_demo(&mut nested),
+            "traits" => traits::run_demo_with(&mut nested, term),
+            _ => lifetimes::run_demo(&mut nested),
+        }
+    };
+    for line in text.lines() {
+        out.line(line.to_string());
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(out: &[String]) -> Vec<&str> {
+        out.iter().map(String::as_str).filter(|line| line.starts_with("== ")).collect()
+    }
+
+    #[test]
+    fn no_section_runs_them_all_in_order() {
+        let mut out: Vec<String> = Vec::new();
+        let reports = run(None, &mut out, RunOptions::default()).unwrap();
+        let names: Vec<&str> = reports.iter().map(|report| report.section).collect();
+        assert_eq!(names, SECTIONS);
+        assert_eq!(headers(&out), ["== generics ==", "== traits ==", "== lifetimes =="]);
+    }
+
+    #[test]
+    fn a_known_section_runs_only_that_one() {
+        let mut out: Vec<String> = Vec::new();
+        let reports = run(Some("lifetimes"), &mut out, RunOptions::default()).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].section, "lifetimes");
+        assert_eq!(headers(&out), ["== lifetimes =="]);
+        //Everything under the header is indented.
+        assert!(out[1..].iter().all(|line| line.is_empty() || line.starts_with("  ")));
+    }
+
+    #[test]
+    fn color_only_reaches_the_header_when_asked_for() {
+        let mut out: Vec<String> = Vec::new();
+        let options = RunOptions { term: Term::new(true), ..RunOptions::default() };
+        run(Some("lifetimes"), &mut out, options).unwrap();
+        assert_eq!(out[0], term::paint("== lifetimes ==", Color::Blue));
+    }
+
+    #[test]
+    fn bench_uses_the_size_it_is_given_and_has_no_report() {
+        let mut out: Vec<String> = Vec::new();
+        let options = RunOptions { bench_size: 10, ..RunOptions::default() };
+        let reports = run(Some(BENCH), &mut out, options).unwrap();
+        assert!(reports.is_empty());
+        //Squares with sides 0 to 9, so the total area is 0 + 1 + 4 + ... + 81.
+        assert!(out[0].starts_with("static dispatch: total 285 in "), "{}", out[0]);
+        assert!(headers(&out).is_empty());
+    }
+
+    #[test]
+    fn an_unknown_section_is_a_usage_error() {
+        let mut out: Vec<String> = Vec::new();
+        let err = run(Some("pentagons"), &mut out, RunOptions::default()).unwrap_err();
+        assert!(matches!(&err, ChapterError::Usage(message) if message.contains(&cli::usage())));
+        assert!(err.to_string().starts_with("unknown section 'pentagons'\n"));
+        assert!(out.is_empty());
+    }
+}
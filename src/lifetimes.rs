@@ -0,0 +1,938 @@
+use crate::arena;
+use crate::expr;
+use crate::interner;
+use crate::output::{self, IndentedWriter, Output};
+use crate::report::SectionReport;
+use crate::traits::{Pair, Square};
+use std::fmt::{Display, Write};
+use std::iter::FusedIterator;
+
+//Every reference has a lifetime, which the the scope for which that reference is valid.
+// lifetimes can be annotated as a method of specifying relationships between lifetimes.
+// I am new to this feature (never seen it in another programming language), and quite frankly
+// the only value I can see to it is in passing a variable through a function without making
+// a copy.
+
+//Essentially the lifetimes are communicated using the lifetime annotation below. This `says`
+// that there is a relationship between the lifetimes of x, y and the return value. The lifetime
+// and the generic are separate things, 'a says how long the references live and T says what
+// they point at. On a tie the first one wins.
+pub fn largest_ref<'a, T: PartialOrd>(x: &'a T, y: &'a T) -> &'a T {
+    if y > x {
+        y
+    } else {
+        x
+    }
+}
+
+//The name this used to have back when it only worked for i32.
+#[deprecated(note = "use largest_ref, which works for any PartialOrd type")]
+pub fn largest_int<'a>(x: &'a i32, y: &'a i32) -> &'a i32 {
+    largest_ref(x, y)
+}
+
+//More parameters don't need more lifetimes. Any of the three could come back, so they all
+// share 'a and the result lives as long as the shortest lived of them. The earliest one wins
+// a tie here as well.
+pub fn largest_of_three<'a, T: PartialOrd>(x: &'a T, y: &'a T, z: &'a T) -> &'a T {
+    largest_ref(largest_ref(x, y), z)
+}
+
+//Because of the above function definition, all lifetimes of the parameters must be valid for
+// the compiler to allow res to be used. For example, the below code will not compile.
+// let int1 = 2;
+// let res;
+// {
+//     let int2 = 1;
+//     res = largest_ref(&int1, &int2);
+// }
+// println!("result: {res}");
+
+//The classic example from the book, but with string slices. On a tie the first one wins.
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if y.len() > x.len() {
+        y
+    } else {
+        x
+    }
+}
+
+//Same as largest_ref, the result can't outlive the shorter lived argument even if the longer
+// one is the one returned. So this won't compile either because `string2` is gone by the
+// println.
+// let string1 = String::from("long string is long");
+// let result;
+// {
+//     let string2 = String::from("xyz");
+//     result = longest(string1.as_str(), string2.as_str());
+// }
+// println!("The longest string is {result}");
+
+//Unlike longest, the result here is always a slice of `a`. The signature still ties it to both
+// arguments since that is what the annotation says, even though only `a` is ever returned from.
+//Characters are compared instead of bytes, so if two strings split apart part way through a
+// multi-byte character the prefix stops before that character instead of cutting it in half.
+pub fn common_prefix<'a>(a: &'a str, b: &'a str) -> &'a str {
+    let end = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|((_, from_a), from_b)| from_a != from_b)
+        .map(|((i, _), _)| i)
+        .unwrap_or(a.len().min(b.len()));
+    &a[..end]
+}
+
+//The prefix shared by every string in the slice, which is a slice of the first one. Nothing at
+// all has an empty prefix.
+pub fn common_prefix_all<'a>(items: &'a [&'a str]) -> &'a str {
+    match items.split_first() {
+        Some((first, rest)) => rest.iter().fold(first, |prefix, item| common_prefix(prefix, item)),
+        None => "",
+    }
+}
+
+//They go through that you cannot return a reference to an object that will be deallocated at
+// the end of a function. But coming from c++ this is the norm so I am not going to focus too
+// much on it.
+
+//structs also require lifetime annotations in order to hold references.
+pub struct MyStuff<'a> {
+    pub my_str: &'a str
+}
+
+//Two references with two separate lifetimes. Nothing says the title and the body have to live
+// for the same amount of time, so either one can be dropped without the other.
+pub struct Excerpts<'a, 'b> {
+    pub title: &'a str,
+    pub body: &'b str,
+}
+
+impl<'a, 'b> Excerpts<'a, 'b> {
+    //Only the title can come out of this one. Putting `self.body` in the else branch doesn't
+    // compile, the body only lives for 'b and nothing says 'b is as long as 'a. Asking for
+    // anything but the title gives back an empty string instead, which lives for 'static and so
+    // is fine for any 'a.
+    pub fn title_or_body(&self, want_title: bool) -> &'a str {
+        if want_title {
+            self.title
+        } else {
+            ""
+        }
+    }
+
+    //The body has to come out of its own method with its own lifetime.
+    pub fn body(&self) -> &'b str {
+        self.body
+    }
+}
+
+//There are also certain exceptions that happen so frequently the compiler can fill in the
+// lifetime annotations for me. These exceptions are called `lifetime elision rules`.
+// There are currently three rules.
+
+//Rule #1: The compiler assigns a lifetime parameter to each parameter that's a reference.
+// For example
+// fn foo<'a>(a: &<'a>i32) { ...
+//  can be written as
+// fn foo(a: &i32) { ...
+
+//Rule #2: If there is exactly one input lifetime parameter, that lifetime is assigned to all
+// output lifetime parameters.
+// For example
+pub fn single_param(x: &i32) -> &i32 {
+    x
+}
+
+//Rule #2 is also why first_word doesn't need any annotations. Leading spaces are skipped and an
+// empty (or all space) string gives back an empty slice.
+pub fn first_word(s: &str) -> &str {
+    Words::new(s).next().unwrap_or("")
+}
+
+pub fn transform_lines<F>(text: &str, f: F) -> Vec<String>
+where
+    F: for<'a> Fn(&'a str) -> &'a str,
+{
+    text.lines().map(|line| f(line).to_string()).collect()
+}
+
+pub fn map_lines<F>(text: &str, f: F) -> Vec<String>
+where
+    F: Fn(&str) -> String,
+{
+    text.lines().map(f).collect()
+}
+
+//An iterator can hand out references too. Every word is a slice of the original string, so the
+// items carry the same 'a as the string the iterator was made from.
+pub struct Words<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Words<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Words { rest: s }
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    //Any number of spaces between (or around) words is skipped over. Once the string runs out
+    // rest stays empty, so every call after that keeps returning None.
+    fn next(&mut self) -> Option<&'a str> {
+        self.rest = self.rest.trim_start_matches(' ');
+        if self.rest.is_empty() {
+            return None;
+        }
+        let end = self.rest.find(' ').unwrap_or(self.rest.len());
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(word)
+    }
+}
+
+impl FusedIterator for Words<'_> {}
+
+//Rule #3: If there are multiple input parameters, but one of them is &self or &mut self
+// (meaning that this is a method) then the lifetime of self is assigned to all output lifetime
+// parameters.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mood {
+    Happy,
+    Sad,
+    Neutral,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    GoodNews,
+    BadNews,
+    Nothing,
+}
+
+//The state pattern from chapter 17 of the book. Each state decides for itself what the next
+// state is, so World never has to match on which state it is in. next takes self: Box<Self> so
+// the old state is used up by the change and can't be held on to afterwards.
+pub trait WorldState {
+    fn next(self: Box<Self>, event: Event) -> Box<dyn WorldState>;
+    fn describe(&self) -> &str;
+    fn mood(&self) -> Mood;
+}
+
+pub struct Happy;
+pub struct Sad;
+pub struct Neutral;
+
+//Good news can't make a happy world any happier and bad news can't make a sad one any sadder,
+// so those stay where they are. Nothing never changes anything.
+impl WorldState for Happy {
+    fn next(self: Box<Self>, event: Event) -> Box<dyn WorldState> {
+        match event {
+            Event::BadNews => Box::new(Neutral),
+            Event::GoodNews | Event::Nothing => self,
+        }
+    }
+
+    fn describe(&self) -> &str {
+        "the world is happy"
+    }
+
+    fn mood(&self) -> Mood {
+        Mood::Happy
+    }
+}
+
+impl WorldState for Sad {
+    fn next(self: Box<Self>, event: Event) -> Box<dyn WorldState> {
+        match event {
+            Event::GoodNews => Box::new(Neutral),
+            Event::BadNews | Event::Nothing => self,
+        }
+    }
+
+    fn describe(&self) -> &str {
+        "the world is sad"
+    }
+
+    fn mood(&self) -> Mood {
+        Mood::Sad
+    }
+}
+
+impl WorldState for Neutral {
+    fn next(self: Box<Self>, event: Event) -> Box<dyn WorldState> {
+        match event {
+            Event::GoodNews => Box::new(Happy),
+            Event::BadNews => Box::new(Sad),
+            Event::Nothing => self,
+        }
+    }
+
+    fn describe(&self) -> &str {
+        "the world is neither happy nor sad"
+    }
+
+    fn mood(&self) -> Mood {
+        Mood::Neutral
+    }
+}
+
+//The lowest happiness level that counts as each mood, checked from the top down. Above 5 is
+// Happy, below -5 is Sad and anything from -5 to 5 (including both ends) is Neutral.
+const MOOD_THRESHOLDS: [(i32, Mood); 3] =
+    [(6, Mood::Happy), (-5, Mood::Neutral), (i32::MIN, Mood::Sad)];
+
+//The state is an Option so handle can take it out, hand it to next and put the new one back.
+// It is only ever None for that moment in the middle of handle. The world can be moved two ways,
+// by events through the states or by changing its happiness level with transition.
+pub struct World {
+    state: Option<Box<dyn WorldState>>,
+    level: i32,
+    thresholds: [(i32, Mood); 3],
+    history: Vec<Mood>,
+}
+
+impl World {
+    //The world starts out Neutral at a happiness level of 0.
+    pub fn new() -> Self {
+        World {
+            state: Some(Box::new(Neutral)),
+            level: 0,
+            thresholds: MOOD_THRESHOLDS,
+            history: vec![Mood::Neutral],
+        }
+    }
+
+    //Writing 'static on the return type overrides rule #3. Without it the strings would only
+    // be allowed to live as long as &self, even though they are literals.
+    pub fn winner(&self, happiness: &i32) -> &'static str {
+        if *happiness > 5 {
+            "happy"
+        } else {
+            "sad"
+        }
+    }
+
+    //The returned reference points into the world's own copy of the thresholds, so rule #3
+    // giving it the lifetime of &self is exactly right here.
+    pub fn assess(&self, happiness: &i32) -> &Mood {
+        let (_, mood) = self
+            .thresholds
+            .iter()
+            .find(|(lowest, _)| happiness >= lowest)
+            .unwrap_or(&self.thresholds[self.thresholds.len() - 1]);
+        mood
+    }
+
+    //Moves the happiness level by delta and jumps straight to whichever state the thresholds
+    // pick for the new level. Every mood the world ends up in gets added to the history.
+    pub fn transition(&mut self, delta: i32) {
+        self.level = self.level.saturating_add(delta);
+        let state: Box<dyn WorldState> = match self.assess(&self.level) {
+            Mood::Happy => Box::new(Happy),
+            Mood::Sad => Box::new(Sad),
+            Mood::Neutral => Box::new(Neutral),
+        };
+        self.history.push(state.mood());
+        self.state = Some(state);
+    }
+
+    //Events only move the world one state at a time and leave the happiness level alone. The
+    // mood is added to the history even when the event didn't change anything.
+    pub fn handle(&mut self, event: Event) {
+        if let Some(state) = self.state.take() {
+            let next = state.next(event);
+            self.history.push(next.mood());
+            self.state = Some(next);
+        }
+    }
+
+    pub fn describe(&self) -> &str {
+        self.state.as_ref().map_or("", |state| state.describe())
+    }
+
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    //The last mood in the history is always the current one.
+    pub fn mood(&self) -> &Mood {
+        self.history.last().unwrap_or(&Mood::Neutral)
+    }
+
+    //Hands out a slice of the history instead of the Vec itself.
+    pub fn history(&self) -> &[Mood] {
+        &self.history
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//The book's version of the same idea. announcement has its own lifetime, but because of &self
+// the returned part gets the lifetime of self, which is really the lifetime of the text that
+// `part` was borrowed from.
+pub struct ImportantExcerpt<'a> {
+    pub part: &'a str,
+}
+
+impl<'a> ImportantExcerpt<'a> {
+    pub fn level(&self) -> i32 {
+        3
+    }
+
+    pub fn announce_and_return_part(&self, announcement: &str, out: &mut dyn Output) -> &str {
+        out.line(format!("Attention please: {announcement}"));
+        self.part
+    }
+}
+
+//As a side note I didn't realise that Rust had different definitions of `Method` and
+// `Function`. I have been using them interchangeably. A Method is a function declared on a
+// struct, enum or trait with the first parameter of self. A function seems to be everything
+// else starting with the `fn` keyword that is not a method.
+
+//The lifetime annotation for impl must be used if a reference type is stored inside the
+// object.
+impl<'a> MyStuff<'a> {
+    pub fn sad(&self) -> i32 {
+        3
+    }
+}
+
+//Holds on to a borrowed document and remembers where every line starts and ends. The methods
+// can't rely on elision here. Rule #3 would give the returned slices the lifetime of &self, so
+// they would die with the index. Writing 'a out says they come from the document instead.
+pub struct DocumentIndex<'a> {
+    document: &'a str,
+    lines: Vec<(usize, usize)>,
+}
+
+impl<'a> DocumentIndex<'a> {
+    //Lines are split the same way str::lines does it, so a trailing newline doesn't add an empty
+    // line at the end and an empty document has no lines at all.
+    pub fn new(document: &'a str) -> Self {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in document.split_inclusive('\n') {
+            let content = line.strip_suffix('\n').unwrap_or(line);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+            lines.push((start, start + content.len()));
+            start += line.len();
+        }
+        DocumentIndex { document, lines }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    //Lines are counted from 0.
+    pub fn line(&self, n: usize) -> Option<&'a str> {
+        let document: &'a str = self.document;
+        self.lines.get(n).map(|&(start, end)| &document[start..end])
+    }
+
+    pub fn lines_containing(&self, needle: &str) -> Vec<&'a str> {
+        (0..self.lines.len())
+            .filter_map(|n| self.line(n))
+            .filter(|line| line.contains(needle))
+            .collect()
+    }
+}
+
+//Everything the config holds is a slice of the text it was parsed from, so building one never
+// allocates a String. The trade off is that the config can't outlive that text.
+pub struct Config<'a> {
+    entries: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Config<'a> {
+    //Whitespace around keys and values is trimmed. Blank lines, lines without an `=` and lines
+    // with nothing before the `=` are skipped. If a key shows up more than once the last value
+    // wins, but the key keeps the spot where it first showed up.
+    pub fn parse(text: &'a str) -> Self {
+        let mut entries: Vec<(&'a str, &'a str)> = Vec::new();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if key.is_empty() {
+                continue;
+            }
+            match entries.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(entry) => entry.1 = value,
+                None => entries.push((key, value)),
+            }
+        }
+        Config { entries }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.entries.iter().find(|(k, _)| *k == key).map(|&(_, value)| value)
+    }
+
+    //The items borrow from the text ('a) but the iterator itself borrows the config ('_).
+    pub fn keys(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.entries.iter().map(|&(key, _)| key)
+    }
+}
+
+//A generic struct holding a reference needs `T: 'a`, which says that whatever T is, it has to
+// live at least as long as the borrow. A T that holds references of its own (like MyStuff) can't
+// be shorter lived than the Holder pointing at it. Newer compilers can infer this bound, but it
+// is written out here to make it visible.
+pub struct Holder<'a, T: 'a> {
+    inner: &'a T,
+}
+
+impl<'a, T: 'a> Holder<'a, T> {
+    pub fn new(inner: &'a T) -> Self {
+        Holder { inner }
+    }
+
+    //The 'a is spelled out, so the reference comes from whatever the holder was pointing at and
+    // is still usable after the holder itself is dropped.
+    pub fn get(&self) -> &'a T {
+        self.inner
+    }
+
+    //How long the new holder can borrow for is decided by what the closure promises. Writing
+    // `Fn(&'a T) -> &'a U` says it is handed the 'a reference and gives back one that lives for
+    // 'a as well, so the result can be a Holder<'a, U> that outlives this holder. Rule #3 would
+    // only tie the result to &self if the return type left its lifetime out, which is why 'a is
+    // written there too. An elided `Fn(&T) -> &U` also works, but it has to hold for every
+    // lifetime, so a closure handing back some other reference that lives for 'a is turned away.
+    pub fn map_ref<U>(&self, f: impl Fn(&'a T) -> &'a U) -> Holder<'a, U> {
+        Holder { inner: f(self.inner) }
+    }
+}
+
+//`'static` is a special lifetime. It allows for the variable to be directly written into the
+// programs binary. When &str is used for a string literal, by default it is set to the static
+// lifetime annotation.
+
+//A lifetime can be used with a generic as follows.
+// fn largest_int<'a, T>(x: &'a i32, y: &'a i32, z: T) -> &'a i32
+// where
+//     T: Display,
+// { ...
+
+//And the real thing combining lifetimes, generics and trait bounds in one signature.
+pub fn longest_with_announcement<'a, T>(
+    x: &'a str,
+    y: &'a str,
+    ann: T,
+    out: &mut dyn Output,
+) -> &'a str
+where
+    T: Display,
+{
+    out.line(format!("Announcement! {ann}"));
+    longest(x, y)
+}
+
+pub fn run_demo(out: &mut dyn Output) -> SectionReport {
+    let mut report = SectionReport::new("lifetimes");
+
+    report.demo("largest_ref");
+    let int1 = 1;
+    let int2 = 2;
+
+    let res = largest_ref(&int1, &int2);
+    out.line(format!("result: {res}"));
+    report.record("result", res);
+
+    //Comparing &String compares the Strings, and that compares their contents as str one byte
+    // at a time. So it is alphabetical (for ascii at least), not by length like longest.
+    let apple = String::from("apple");
+    let pear = String::from("pear");
+    let banana = String::from("banana");
+    out.line(format!("largest of three strings: {}", largest_of_three(&apple, &pear, &banana)));
+    out.line(format!("largest of three ints: {}", largest_of_three(&3, &9, &4)));
+    let squares = [Square { height: 2 }, Square { height: 7 }, Square { height: 5 }];
+    let biggest = largest_of_three(&squares[0], &squares[1], &squares[2]);
+    out.line(format!("largest of three squares has height {}", biggest.height));
+
+    //Which of the three references came back, to see who wins a tie.
+    let position = |found: &i32, all: [&i32; 3]| all.iter().position(|r| std::ptr::eq(*r, found));
+    for (x, y, z) in [(5, 5, 5), (5, 5, 1), (1, 5, 5), (5, 1, 5)] {
+        let winner = largest_of_three(&x, &y, &z);
+        out.line(format!("{x} {y} {z}: position {:?} wins", position(winner, [&x, &y, &z])));
+    }
+
+    report.demo("longest");
+    let string1 = String::from("abcd");
+    out.line(format!("longest: {}", longest(string1.as_str(), "xyz")));
+    out.line(format!("longest: {}", longest("same", "size")));
+    let announced = longest_with_announcement(string1.as_str(), "xyz", "comparing lengths", out);
+    out.line(format!("longest: {announced}"));
+    report.record("longest", announced);
+
+    report.demo("common prefix");
+    out.line(format!("common prefix: {:?}", common_prefix("lifetime", "lifeline")));
+    out.line(format!("common prefix: {:?}", common_prefix("naïve", "naïve")));
+    out.line(format!("common prefix: {:?}", common_prefix("héllo", "hèllo")));
+    out.line(format!("common prefix: {:?}", common_prefix("abc", "xyz")));
+    let words = ["interstellar", "internet", "interval"];
+    out.line(format!("common prefix of {:?}: {:?}", words, common_prefix_all(&words)));
+
+    report.demo("struct lifetimes");
+    let my_str = String::from("the string");
+    let stuff = MyStuff{my_str: my_str.as_str()};
+
+    out.line(format!("stuff string: {}", stuff.my_str));
+
+    report.demo("two lifetimes");
+    let title;
+    {
+        let body = String::from("It was the best of times, it was the worst of times.");
+        let excerpts = Excerpts { title: "A Tale of Two Cities", body: &body };
+        out.line(format!("body: {}", excerpts.body()));
+        title = excerpts.title_or_body(true);
+    }
+    //body has been dropped, but the title is from a literal and is still around.
+    out.line(format!("title: {title}"));
+
+    report.demo("elision");
+    out.line(format!("single_param: {}", single_param(&int1)));
+
+    report.demo("words");
+    let sentence = String::from("  the quick   brown fox ");
+    out.line(format!("first_word: {}", first_word(&sentence)));
+    let words: Vec<&str> = Words::new(&sentence).collect();
+    out.line(format!("words: {:?}", words));
+
+    report.demo("transform lines");
+    let poem = String::from("  roses are red  \n   \nviolets are blue\n");
+    out.line(format!("trimmed: {:?}", transform_lines(&poem, str::trim)));
+    out.line(format!("first words: {:?}", transform_lines(&poem, first_word)));
+    out.line(format!("shouted: {:?}", map_lines(&poem, |line| line.trim().to_uppercase())));
+
+    report.demo("world");
+    let mut world = World::new();
+
+    let happiness = 10;
+    out.line(format!("winner: {}", world.winner(&happiness)));
+    out.line(format!("assess: {:?}", world.assess(&happiness)));
+
+    let events = [Event::GoodNews, Event::GoodNews, Event::BadNews, Event::BadNews, Event::Nothing];
+    for event in events {
+        world.handle(event);
+        out.line(format!("after {:?}: {}", event, world.describe()));
+    }
+    for delta in [5, 3, -20] {
+        world.transition(delta);
+        out.line(format!("after {delta:+}: level {} {}", world.level(), world.describe()));
+    }
+    out.line(format!("mood: {:?} history: {:?}", world.mood(), world.history()));
+
+    report.demo("important excerpt");
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let first_sentence = novel.split('.').next().expect("Could not find a '.'");
+    let excerpt = ImportantExcerpt{part: first_sentence};
+    out.line(format!("excerpt level: {}", excerpt.level()));
+    let part = excerpt.announce_and_return_part("here is the excerpt", out);
+    out.line(format!("excerpt: {part}"));
+
+    report.demo("lifetimes on impl");
+    out.line(format!("sad: {}", stuff.sad()));
+
+    report.demo("document index");
+    let document = String::from("Rust is fast.\nRust is safe.\n\nLifetimes are strange.\n");
+    let (second_line, rust_lines) = {
+        let index = DocumentIndex::new(&document);
+        out.line(format!("document lines: {} line 10: {:?}", index.line_count(), index.line(10)));
+        (index.line(1), index.lines_containing("Rust"))
+    };
+    //The index is gone at this point, but the slices borrowed from document are still fine.
+    out.line(format!("second line: {:?} lines with Rust: {:?}", second_line, rust_lines));
+
+    report.demo("config");
+    let text = String::from("name = chapter 10\n\nno equals sign\n = no key\nlevel=3\nname=ten\n");
+    let name = {
+        let config = Config::parse(&text);
+        out.line(format!("keys: {:?}", config.keys().collect::<Vec<_>>()));
+        out.line(format!("level: {:?} missing: {:?}", config.get("level"), config.get("missing")));
+        config.get("name")
+    };
+    //Same as the document index, the value is a slice of text so it outlives the config.
+    out.line(format!("name: {:?}", name));
+
+    report.demo("holder");
+    let pair = Pair::new(3, 7);
+    let name = String::from("holder of a string");
+    let (pair_ref, name_ref) = {
+        let pair_holder = Holder::new(&pair);
+        let name_holder = Holder::new(&name);
+        let x_holder = pair_holder.map_ref(|p| &p.x);
+        out.line(format!("mapped x: {}", x_holder.get()));
+        (pair_holder.get(), name_holder.get())
+    };
+    //Both holders are gone, but what get returned borrows from pair and name directly.
+    out.line(format!("pair: ({}, {}) name: {}", pair_ref.x, pair_ref.y, name_ref));
+
+    report.demo("arena");
+    arena::run_demo(out);
+
+    report.demo("expressions");
+    expr::run_demo(out);
+
+    report.demo("indented writer");
+    let mut text = String::new();
+    {
+        let mut shapes = output::section(&mut text, "shapes:");
+        //One line split over three writes, then two lines in one, then nothing at all.
+        let _ = write!(shapes, "squ");
+        let _ = write!(shapes, "are: ");
+        let _ = writeln!(shapes, "100");
+        let _ = write!(shapes, "triangle: 25\n\ncircle: 3\n");
+        let _ = write!(shapes, "");
+        let mut deeper = IndentedWriter::new(&mut shapes, 1);
+        let _ = writeln!(deeper, "nested twice");
+    }
+    for line in text.lines() {
+        out.line(format!("{:?}", line));
+    }
+
+    report.demo("interning");
+    interner::run_demo(out);
+
+    report.demo("static");
+    let hello_world = "Hello world!";
+    let static_int: &'static i32 = &123; //A manual example of 'static.
+
+    out.line(format!("hello_world: {hello_world} static_int: {static_int}"));
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mapped_holder_outlives_the_original() {
+        let pair = Pair::new(3, 7);
+        let x_holder = {
+            let pair_holder = Holder::new(&pair);
+            pair_holder.map_ref(|p| &p.x)
+        };
+        assert_eq!(*x_holder.get(), 3);
+    }
+
+    #[test]
+    fn map_ref_can_return_other_references_that_live_for_a() {
+        let pair = Pair::new(3, 7);
+        let name = String::from("name");
+        let name_ref = &name;
+        let name_holder = {
+            let pair_holder = Holder::new(&pair);
+            pair_holder.map_ref(|_| name_ref)
+        };
+        assert_eq!(name_holder.get(), "name");
+    }
+
+    #[test]
+    fn get_outlives_the_holder() {
+        let name = String::from("holder of a string");
+        let name_ref = {
+            let holder = Holder::new(&name);
+            holder.get()
+        };
+        assert_eq!(name_ref, "holder of a string");
+    }
+
+    #[test]
+    fn the_first_argument_wins_a_tie() {
+        let (a, b, c) = (2, 2, 1);
+        assert!(std::ptr::eq(largest_ref(&a, &b), &a));
+        assert!(std::ptr::eq(largest_of_three(&c, &a, &b), &a));
+        assert_eq!(largest_of_three(&1.5, &3.5, &2.5), &3.5);
+        assert_eq!(longest("abc", "xyz"), "abc");
+        assert_eq!(longest("ab", "xyz"), "xyz");
+        #[allow(deprecated)]
+        let old = largest_int(&4, &9);
+        assert_eq!(*old, 9);
+    }
+
+    #[test]
+    fn common_prefix_stops_on_a_char_boundary() {
+        assert_eq!(common_prefix("interstellar", "internet"), "inter");
+        assert_eq!(common_prefix("same", "same"), "same");
+        assert_eq!(common_prefix("short", "shorter"), "short");
+        assert_eq!(common_prefix("", "anything"), "");
+        assert_eq!(common_prefix("caf\u{e9}", "caf\u{e8}"), "caf");
+        assert_eq!(common_prefix_all(&["flower", "flow", "flight"]), "fl");
+        assert_eq!(common_prefix_all(&["alone"]), "alone");
+        assert_eq!(common_prefix_all(&[]), "");
+    }
+
+    #[test]
+    fn excerpts_keep_their_own_lifetimes() {
+        let title = String::from("Moby Dick");
+        let body_ref = {
+            let body = String::from("Call me Ishmael.");
+            let excerpts = Excerpts { title: &title, body: &body };
+            assert_eq!(excerpts.title_or_body(false), "");
+            assert_eq!(excerpts.body(), "Call me Ishmael.");
+            excerpts.title_or_body(true)
+        };
+        assert_eq!(body_ref, "Moby Dick");
+    }
+
+    #[test]
+    fn words_skip_any_number_of_spaces() {
+        let words: Vec<&str> = Words::new("  the   quick brown ").collect();
+        assert_eq!(words, ["the", "quick", "brown"]);
+        let mut empty = Words::new("   ");
+        assert_eq!((empty.next(), empty.next()), (None, None));
+        assert_eq!(first_word("  hello world"), "hello");
+        assert_eq!(first_word(""), "");
+        assert_eq!(*single_param(&8), 8);
+    }
+
+    #[test]
+    fn words_are_slices_of_the_original_string() {
+        let text = String::from("  the   quick brown ");
+        let offsets: Vec<usize> = Words::new(&text)
+            .map(|word| word.as_ptr() as usize - text.as_ptr() as usize)
+            .collect();
+        assert_eq!(offsets, [2, 8, 14]);
+        let mut words = Words::new(&text);
+        assert_eq!(words.by_ref().count(), 3);
+        assert_eq!((words.next(), words.next()), (None, None));
+    }
+
+    #[test]
+    fn lines_can_be_borrowed_or_owned() {
+        let text = "  one \ntwo  ";
+        assert_eq!(transform_lines(text, str::trim), ["one", "two"]);
+        assert_eq!(map_lines(text, |line| line.trim().to_uppercase()), ["ONE", "TWO"]);
+        assert!(transform_lines("", str::trim).is_empty());
+    }
+
+    #[test]
+    fn first_word_works_as_a_line_transform() {
+        let text = "  hello world\n   \nfoo";
+        assert_eq!(transform_lines(text, first_word), ["hello", "", "foo"]);
+        assert_eq!(transform_lines(" \n\t", str::trim), ["", ""]);
+    }
+
+    #[test]
+    fn the_world_changes_mood_one_step_at_a_time() {
+        let mut world = World::default();
+        assert_eq!(*world.mood(), Mood::Neutral);
+        let steps = [
+            (Event::GoodNews, "the world is happy"),
+            (Event::GoodNews, "the world is happy"),
+            (Event::Nothing, "the world is happy"),
+            (Event::BadNews, "the world is neither happy nor sad"),
+            (Event::BadNews, "the world is sad"),
+            (Event::BadNews, "the world is sad"),
+            (Event::GoodNews, "the world is neither happy nor sad"),
+        ];
+        for (event, described) in steps {
+            world.handle(event);
+            assert_eq!(world.describe(), described, "after {event:?}");
+        }
+        assert_eq!(world.level(), 0);
+        assert_eq!(world.history().len(), steps.len() + 1);
+    }
+
+    #[test]
+    fn happiness_is_neutral_up_to_five_either_way() {
+        let world = World::new();
+        let moods: Vec<Mood> = [-6, -5, 5, 6].iter().map(|h| *world.assess(h)).collect();
+        assert_eq!(moods, [Mood::Sad, Mood::Neutral, Mood::Neutral, Mood::Happy]);
+        assert_eq!(*world.assess(&i32::MIN), Mood::Sad);
+        assert_eq!((world.winner(&5), world.winner(&6)), ("sad", "happy"));
+    }
+
+    #[test]
+    fn transitions_follow_the_thresholds() {
+        let mut world = World::new();
+        for delta in [5, 1, -1, -10, -1, 20] {
+            world.transition(delta);
+        }
+        assert_eq!(world.level(), 14);
+        assert_eq!(
+            world.history(),
+            [
+                Mood::Neutral,
+                Mood::Neutral,
+                Mood::Happy,
+                Mood::Neutral,
+                Mood::Neutral,
+                Mood::Sad,
+                Mood::Happy
+            ]
+        );
+        assert_eq!((*world.mood(), world.describe()), (Mood::Happy, "the world is happy"));
+    }
+
+    #[test]
+    fn events_pick_up_from_where_a_transition_left_off() {
+        let mut world = World::new();
+        world.transition(-6);
+        world.handle(Event::GoodNews);
+        assert_eq!(*world.mood(), Mood::Neutral);
+        world.transition(i32::MIN);
+        assert_eq!((world.level(), *world.mood()), (i32::MIN, Mood::Sad));
+    }
+
+    #[test]
+    fn important_excerpt_announces_and_returns_its_part() {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+        let excerpt = ImportantExcerpt { part: novel.split('.').next().unwrap() };
+        let mut out: Vec<String> = Vec::new();
+        assert_eq!(excerpt.announce_and_return_part("hey", &mut out), "Call me Ishmael");
+        assert_eq!(out, ["Attention please: hey"]);
+        assert_eq!(excerpt.level(), 3);
+        let mut out: Vec<String> = Vec::new();
+        assert_eq!(longest_with_announcement("ab", "abc", 42, &mut out), "abc");
+        assert_eq!(out, ["Announcement! 42"]);
+    }
+
+    #[test]
+    fn document_lines_outlive_the_index() {
+        let document = String::from("first line\r\nsecond\n\nthird line\n");
+        let found = {
+            let index = DocumentIndex::new(&document);
+            assert_eq!(index.line_count(), 4);
+            assert_eq!(index.line(0), Some("first line"));
+            assert_eq!(index.line(2), Some(""));
+            assert_eq!(index.line(4), None);
+            index.lines_containing("line")
+        };
+        assert_eq!(found, ["first line", "third line"]);
+        assert_eq!(DocumentIndex::new("").line_count(), 0);
+    }
+
+    #[test]
+    fn config_keeps_the_last_value_in_the_first_spot() {
+        let text = "name = demo\n\nbroken line\n = nokey\nsize=3\nname= again ";
+        let config = Config::parse(text);
+        assert_eq!(config.get("name"), Some("again"));
+        assert_eq!(config.get("size"), Some("3"));
+        assert_eq!(config.get("broken line"), None);
+        assert_eq!(config.keys().collect::<Vec<_>>(), ["name", "size"]);
+    }
+
+    #[test]
+    fn config_values_point_into_the_text() {
+        let text = String::from("name = demo\nsize=3\nname= again ");
+        let config = Config::parse(&text);
+        let offset = |part: &str| part.as_ptr() as usize - text.as_ptr() as usize;
+        assert_eq!(config.get("name").map(offset), text.find("again"));
+        assert_eq!(config.get("size").map(offset), text.find('3'));
+        let keys: Vec<usize> = config.keys().map(offset).collect();
+        assert_eq!(keys, [0, 12]);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+//Anything that wants to know when a watched shape changes size.
+pub trait ShapeObserver {
+    fn on_change(&mut self, name: &str, old_area: isize, new_area: isize);
+}
+
+//WatchedShape has to be able to change a shape without knowing what it is, so the mutators are
+// traits of their own. Square only has a height, Triangle has both.
+pub trait AdjustHeight {
+    fn height(&self) -> isize;
+    fn set_height(&mut self, height: isize);
+}
+
+pub trait AdjustBase {
+    fn base(&self) -> isize;
+    fn set_base(&mut self, base: isize);
+}
+
+impl AdjustHeight for Square {
+    fn height(&self) -> isize {
+        self.height
+    }
+
+    fn set_height(&mut self, height: isize) {
+        self.height = height;
+    }
+}
+
+impl AdjustHeight for Triangle {
+    fn height(&self) -> isize {
+        self.height
+    }
+
+    fn set_height(&mut self, height: isize) {
+        self.height = height;
+    }
+}
+
+impl AdjustBase for Triangle {
+    fn base(&self) -> isize {
+        self.base
+    }
+
+    fn set_base(&mut self, base: isize) {
+        self.base = base;
+    }
+}
+
+//Wraps a shape so every change goes through it. Observers are told about a change in the order
+// they were added, and setting a dimension to the value it already has isn't a change so
+// nobody is told.
+pub struct WatchedShape<S: Shape> {
+    name: String,
+    shape: S,
+    observers: Vec<Box<dyn ShapeObserver>>,
+}
+
+impl<S: Shape> WatchedShape<S> {
+    pub fn new(name: impl Into<String>, shape: S) -> Self {
+        WatchedShape { name: name.into(), shape, observers: Vec::new() }
+    }
+
+    pub fn watch(&mut self, observer: Box<dyn ShapeObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    //Does the change and tells everyone about it. The areas passed along are from right before
+    // and right after the change.
+    fn change(&mut self, apply: impl FnOnce(&mut S)) {
+        let old_area = self.shape.area();
+        apply(&mut self.shape);
+        let new_area = self.shape.area();
+        for observer in self.observers.iter_mut() {
+            observer.on_change(&self.name, old_area, new_area);
+        }
+    }
+}
+
+impl<S: Shape + AdjustHeight> WatchedShape<S> {
+    pub fn set_height(&mut self, height: isize) {
+        if self.shape.height() != height {
+            self.change(|shape| shape.set_height(height));
+        }
+    }
+}
+
+impl<S: Shape + AdjustBase> WatchedShape<S> {
+    pub fn set_base(&mut self, base: isize) {
+        if self.shape.base() != base {
+            self.change(|shape| shape.set_base(base));
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeEvent {
+    pub name: String,
+    pub old_area: isize,
+    pub new_area: isize,
+}
+
+//Once an observer is boxed up inside a WatchedShape there's no getting it back out. The events
+// are kept behind an Rc instead, so a clone of the recorder can be handed over while the
+// original is kept around to look at what was recorded.
+#[derive(Clone, Default)]
+pub struct RecordingObserver {
+    events: Rc<RefCell<Vec<ShapeEvent>>>,
+}
+
+impl RecordingObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<ShapeEvent> {
+        self.events.borrow().clone()
+    }
+}
+
+impl ShapeObserver for RecordingObserver {
+    fn on_change(&mut self, name: &str, old_area: isize, new_area: isize) {
+        self.events.borrow_mut().push(ShapeEvent { name: name.to_string(), old_area, new_area });
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let recorder = RecordingObserver::new();
+    let mut square = WatchedShape::new("square", Square { height: 10 });
+    square.watch(Box::new(recorder.clone()));
+    square.set_height(12);
+    square.set_height(12);
+    square.set_height(5);
+
+    let mut triangle = WatchedShape::new("triangle", Triangle { base: 5, height: 10 });
+    triangle.watch(Box::new(recorder.clone()));
+    triangle.set_base(8);
+
+    for event in recorder.events() {
+        out.line(format!("{} went from {} to {}", event.name, event.old_area, event.new_area));
+    }
+    out.line(format!("square ended up with area {}", square.shape().area()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, old_area: isize, new_area: isize) -> ShapeEvent {
+        ShapeEvent { name: String::from(name), old_area, new_area }
+    }
+
+    //Writes its own tag into a shared log, so the order observers are told in can be checked.
+    struct Tagged(&'static str, Rc<RefCell<Vec<&'static str>>>);
+
+    impl ShapeObserver for Tagged {
+        fn on_change(&mut self, _: &str, _: isize, _: isize) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    #[test]
+    fn changes_are_recorded_with_the_areas_either_side() {
+        let recorder = RecordingObserver::new();
+        let mut square = WatchedShape::new("square", Square { height: 10 });
+        square.watch(Box::new(recorder.clone()));
+        square.set_height(12);
+        square.set_height(5);
+        assert_eq!(recorder.events(), [event("square", 100, 144), event("square", 144, 25)]);
+        assert_eq!(square.shape().height, 5);
+    }
+
+    #[test]
+    fn setting_the_same_value_is_not_a_change() {
+        let recorder = RecordingObserver::new();
+        let mut triangle = WatchedShape::new("triangle", Triangle { base: 5, height: 10 });
+        triangle.watch(Box::new(recorder.clone()));
+        triangle.set_base(5);
+        triangle.set_height(10);
+        assert!(recorder.events().is_empty());
+        triangle.set_base(8);
+        assert_eq!(recorder.events(), [event("triangle", 25, 40)]);
+    }
+
+    #[test]
+    fn a_change_with_the_same_area_is_still_reported() {
+        let recorder = RecordingObserver::new();
+        let mut triangle = WatchedShape::new("triangle", Triangle { base: 1, height: 2 });
+        triangle.watch(Box::new(recorder.clone()));
+        //A height of 2 or 3 both give an area of 1 once the half is rounded down.
+        triangle.set_height(3);
+        assert_eq!(recorder.events(), [event("triangle", 1, 1)]);
+    }
+
+    #[test]
+    fn observers_are_told_in_the_order_they_were_added() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut square = WatchedShape::new("square", Square { height: 1 });
+        square.watch(Box::new(Tagged("first", Rc::clone(&log))));
+        square.watch(Box::new(Tagged("second", Rc::clone(&log))));
+        square.set_height(2);
+        square.set_height(3);
+        assert_eq!(*log.borrow(), ["first", "second", "first", "second"]);
+    }
+
+    #[test]
+    fn nobody_watching_still_changes_the_shape() {
+        let mut square = WatchedShape::new("square", Square { height: 1 });
+        square.set_height(4);
+        assert_eq!(square.shape().area(), 16);
+    }
+}
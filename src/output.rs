@@ -0,0 +1,117 @@
+use std::fmt::{self, Write};
+
+//Everything the demos print goes through an Output instead of straight to println!. That way
+// the same code can write to the terminal or be collected up and checked afterwards.
+pub trait Output {
+    fn line(&mut self, s: String);
+}
+
+//Prints each line as it comes in, this is what the binary uses.
+pub struct Stdout;
+
+impl Output for Stdout {
+    fn line(&mut self, s: String) {
+        println!("{s}");
+    }
+}
+
+//Keeps every line so it can be looked at later.
+impl Output for Vec<String> {
+    fn line(&mut self, s: String) {
+        self.push(s);
+    }
+}
+
+//Puts level * 2 spaces in front of every line written through it. A line can arrive in pieces
+// over several calls to write_str, so it has to remember whether the last piece finished a line
+// to know if the next one needs the spaces. Empty lines are left empty instead of getting
+// spaces on their own.
+pub struct IndentedWriter<'a, W: Write> {
+    inner: &'a mut W,
+    level: usize,
+    at_line_start: bool,
+}
+
+impl<'a, W: Write> IndentedWriter<'a, W> {
+    pub fn new(inner: &'a mut W, level: usize) -> Self {
+        IndentedWriter { inner, level, at_line_start: true }
+    }
+}
+
+impl<W: Write> Write for IndentedWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for piece in s.split_inclusive('\n') {
+            if self.at_line_start && piece != "\n" {
+                write!(self.inner, "{:width$}", "", width = self.level * 2)?;
+            }
+            self.inner.write_str(piece)?;
+            self.at_line_start = piece.ends_with('\n');
+        }
+        Ok(())
+    }
+}
+
+//Output has no way to report a failed write, so the error is dropped. Writing to a String, which
+// is what this is used with, can't fail anyway.
+impl<W: Write> Output for IndentedWriter<'_, W> {
+    fn line(&mut self, s: String) {
+        let _ = writeln!(self, "{s}");
+    }
+}
+
+//Writes the title and hands back a writer for everything that goes under it. The writer borrows
+// w mutably, so nothing else can write to w until the section is finished with.
+pub fn section<'a, W: Write>(w: &'a mut W, title: &str) -> IndentedWriter<'a, W> {
+    let _ = writeln!(w, "{title}");
+    IndentedWriter::new(w, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec_collects_every_line() {
+        let mut out: Vec<String> = Vec::new();
+        out.line(String::from("one"));
+        out.line(String::new());
+        assert_eq!(out, ["one", ""]);
+    }
+
+    #[test]
+    fn indents_every_line_but_leaves_empty_ones_empty() {
+        let mut text = String::new();
+        let mut writer = IndentedWriter::new(&mut text, 2);
+        writer.write_str("a\n\nb\n").unwrap();
+        assert_eq!(text, "    a\n\n    b\n");
+    }
+
+    #[test]
+    fn a_line_written_in_pieces_is_only_indented_once() {
+        let mut text = String::new();
+        let mut writer = IndentedWriter::new(&mut text, 1);
+        write!(writer, "x = ").unwrap();
+        write!(writer, "{}\ny", 1).unwrap();
+        writer.write_str(" = 2\n").unwrap();
+        assert_eq!(text, "  x = 1\n  y = 2\n");
+    }
+
+    #[test]
+    fn level_zero_changes_nothing() {
+        let mut text = String::new();
+        IndentedWriter::new(&mut text, 0).line(String::from("flat"));
+        assert_eq!(text, "flat\n");
+    }
+
+    #[test]
+    fn sections_nest() {
+        let mut text = String::new();
+        {
+            let mut outer = section(&mut text, "== outer ==");
+            outer.line(String::from("before"));
+            let mut inner = section(&mut outer, "-- inner --");
+            inner.line(String::from("deep"));
+        }
+        assert_eq!(text, "== outer ==\n  before\n  -- inner --\n    deep\n");
+    }
+}
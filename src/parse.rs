@@ -0,0 +1,187 @@
+use crate::error::{error_chain, ChapterError, WithContext};
+use crate::output::Output;
+use crate::traits::{Shape, ShapeError, Square, Triangle};
+use std::fmt::Display;
+use std::str::FromStr;
+
+//Shapes written as text, for example `square:10` or `triangle:5x10` (base x height).
+#[derive(Debug, PartialEq)]
+pub enum ParseShapeError {
+    UnknownShape(String),
+    MissingField(&'static str),
+    InvalidNumber(String),
+    Invalid(ShapeError),
+}
+
+impl Display for ParseShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseShapeError::UnknownShape(name) => write!(f, "unknown shape '{name}'"),
+            ParseShapeError::MissingField(field) => write!(f, "missing field '{field}'"),
+            ParseShapeError::InvalidNumber(value) => write!(f, "'{value}' is not a number"),
+            ParseShapeError::Invalid(e) => write!(f, "invalid shape: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseShapeError {}
+
+impl From<ShapeError> for ParseShapeError {
+    fn from(e: ShapeError) -> Self {
+        ParseShapeError::Invalid(e)
+    }
+}
+
+//An empty field counts as missing rather than as a bad number.
+fn parse_field(value: Option<&str>, field: &'static str) -> Result<isize, ParseShapeError> {
+    let value = value.map(str::trim).unwrap_or("");
+    if value.is_empty() {
+        return Err(ParseShapeError::MissingField(field));
+    }
+    value.parse().map_err(|_| ParseShapeError::InvalidNumber(value.to_string()))
+}
+
+//Splits `name:fields` apart. Whitespace around the whole thing or either part is ignored.
+fn split_name(s: &str) -> (&str, Option<&str>) {
+    match s.trim().split_once(':') {
+        Some((name, fields)) => (name.trim(), Some(fields)),
+        None => (s.trim(), None),
+    }
+}
+
+impl FromStr for Square {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_name(s) {
+            ("square", height) => Ok(Square::new(parse_field(height, "height")?)?),
+            (name, _) => Err(ParseShapeError::UnknownShape(name.to_string())),
+        }
+    }
+}
+
+impl FromStr for Triangle {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match split_name(s) {
+            ("triangle", fields) => {
+                let mut fields = fields.unwrap_or("").splitn(2, 'x');
+                let base = parse_field(fields.next(), "base")?;
+                let height = parse_field(fields.next(), "height")?;
+                Ok(Triangle::new(base, height)?)
+            }
+            (name, _) => Err(ParseShapeError::UnknownShape(name.to_string())),
+        }
+    }
+}
+
+//The name decides which concrete type to parse into, after that it is just a Box<dyn Shape>.
+pub fn parse_shape(s: &str) -> Result<Box<dyn Shape>, ParseShapeError> {
+    match split_name(s).0 {
+        "square" => Ok(Box::new(s.parse::<Square>()?)),
+        "triangle" => Ok(Box::new(s.parse::<Triangle>()?)),
+        name => Err(ParseShapeError::UnknownShape(name.to_string())),
+    }
+}
+
+//Every line has to parse, the first one that doesn't stops the whole config.
+pub fn parse_config(lines: &[&str]) -> Result<Vec<Box<dyn Shape>>, ChapterError> {
+    lines
+        .iter()
+        .map(|line| parse_shape(line))
+        .collect::<Result<_, _>>()
+        .context("while parsing shape config")
+}
+
+//Going the other way, these give back a string that parses into the same shape.
+impl Square {
+    pub fn to_config_string(&self) -> String {
+        format!("square:{}", self.height)
+    }
+}
+
+impl Triangle {
+    pub fn to_config_string(&self) -> String {
+        format!("triangle:{}x{}", self.base, self.height)
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let config = ["square:10", " triangle: 5x10 ", "circle:3", "triangle:5", "square:ten"];
+    for line in config {
+        match parse_shape(line) {
+            Ok(shape) => out.line(format!("parsed '{line}' with area {}", shape.area())),
+            Err(e) => out.line(format!("skipping '{line}': {e}")),
+        }
+    }
+
+    let square = Square { height: 7 };
+    let config_string = square.to_config_string();
+    match config_string.parse::<Square>() {
+        Ok(parsed) => out.line(format!("{config_string} parses back into {parsed}")),
+        Err(e) => out.line(format!("{config_string} did not parse back: {e}")),
+    }
+
+    for config in [Vec::from(["square:2", "triangle:3x4"]), Vec::from(["square:2", "pentagon:5"])] {
+        match parse_config(&config) {
+            Ok(shapes) => out.line(format!("parsed a config of {} shapes", shapes.len())),
+            Err(e) => {
+                let mut lines = error_chain(&e).into_iter();
+                out.line(format!("error: {}", lines.next().unwrap_or_default()));
+                lines.for_each(|line| out.line(line));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_squares_and_triangles_ignoring_whitespace() {
+        assert_eq!("square:10".parse::<Square>(), Ok(Square { height: 10 }));
+        assert_eq!(" triangle : 5 x 10 ".parse::<Triangle>(), Ok(Triangle { base: 5, height: 10 }));
+        assert_eq!(parse_shape("triangle:3x4").map(|shape| shape.area()), Ok(6));
+    }
+
+    #[test]
+    fn each_kind_of_mistake() {
+        let unknown = ParseShapeError::UnknownShape(String::from("circle"));
+        assert_eq!("circle:3".parse::<Square>(), Err(unknown));
+        assert_eq!("square".parse::<Square>(), Err(ParseShapeError::MissingField("height")));
+        assert_eq!("square: ".parse::<Square>(), Err(ParseShapeError::MissingField("height")));
+        assert_eq!("triangle:5".parse::<Triangle>(), Err(ParseShapeError::MissingField("height")));
+        assert_eq!("triangle:x5".parse::<Triangle>(), Err(ParseShapeError::MissingField("base")));
+        let ten = ParseShapeError::InvalidNumber(String::from("ten"));
+        assert_eq!("square:ten".parse::<Square>(), Err(ten));
+        let invalid = ParseShapeError::Invalid(ShapeError::NonPositiveHeight(0));
+        assert_eq!("square:0".parse::<Square>(), Err(invalid));
+    }
+
+    #[test]
+    fn a_square_is_not_a_triangle() {
+        let err = "square:3".parse::<Triangle>().unwrap_err();
+        assert_eq!(err.to_string(), "unknown shape 'square'");
+    }
+
+    #[test]
+    fn config_strings_round_trip() {
+        let square = Square { height: 7 };
+        assert_eq!(square.to_config_string().parse::<Square>(), Ok(square));
+        let triangle = Triangle { base: 3, height: 9 };
+        assert_eq!(triangle.to_config_string(), "triangle:3x9");
+        assert_eq!(triangle.to_config_string().parse::<Triangle>(), Ok(triangle));
+    }
+
+    #[test]
+    fn config_stops_at_the_first_bad_line() {
+        let shapes = parse_config(&["square:2", "triangle:3x4"]).unwrap();
+        assert_eq!(shapes.iter().map(|shape| shape.area()).collect::<Vec<_>>(), [4, 6]);
+        assert!(parse_config(&[]).unwrap().is_empty());
+        let err = parse_config(&["square:2", "pentagon:5", "square:x"]).unwrap_err();
+        let expected = "could not parse shape: unknown shape 'pentagon'";
+        assert_eq!(err.to_string(), format!("while parsing shape config: {expected}"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::output::Output;
+use crate::traits::{Shape, ShapeInfo, Square};
+use std::any::Any;
+
+//Const generics let the number of sides be part of the type, so RegularPolygon<6> and
+// RegularPolygon<4> are as different to the compiler as Square and Triangle.
+#[derive(Debug, Clone)]
+pub struct RegularPolygon<const N: usize> {
+    side: f64,
+}
+
+impl<const N: usize> RegularPolygon<N> {
+    //Checked at compile time. Using new with N below 3 fails to build, for example the below does
+    // not compile.
+    // RegularPolygon::<2>::new(1.0);
+    const AT_LEAST_THREE_SIDES: () = assert!(N >= 3, "a polygon needs at least 3 sides");
+
+    pub fn new(side: f64) -> Self {
+        let () = Self::AT_LEAST_THREE_SIDES;
+        RegularPolygon { side }
+    }
+
+    pub fn side(&self) -> f64 {
+        self.side
+    }
+}
+
+impl<const N: usize> Shape for RegularPolygon<N> {
+    fn area(&self) -> isize {
+        self.area_f64().round() as isize
+    }
+
+    //N * side^2 / (4 * tan(pi / N)), the tangent on the bottom is where the cotangent comes in.
+    fn area_f64(&self) -> f64 {
+        let n = N as f64;
+        n * self.side * self.side / (4.0 * (std::f64::consts::PI / n).tan())
+    }
+
+    fn perimeter(&self) -> f64 {
+        N as f64 * self.side
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+//The number of sides is already part of the type, so it only has to be passed along.
+impl<const N: usize> ShapeInfo for RegularPolygon<N> {
+    const SIDES: usize = N;
+    const NAME: &'static str = "regular polygon";
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let hexagon = RegularPolygon::<6>::new(2.0);
+    out.line(format!(
+        "hexagon with side {} has area {} and perimeter {}",
+        hexagon.side(),
+        hexagon.area_f64(),
+        hexagon.perimeter()
+    ));
+
+    //A four sided regular polygon is just a square, so they should agree (give or take float
+    // rounding).
+    let square = Square { height: 10 };
+    let four_sided = RegularPolygon::<4>::new(10.0);
+    out.line(format!(
+        "square area: {} four sided polygon area: {}",
+        square.area(),
+        four_sided.area()
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn four_sides_is_a_square() {
+        let four_sided = RegularPolygon::<4>::new(10.0);
+        assert!(close(four_sided.area_f64(), 100.0));
+        assert_eq!(four_sided.area(), Square { height: 10 }.area());
+        assert_eq!(four_sided.perimeter(), 40.0);
+    }
+
+    #[test]
+    fn triangle_and_hexagon_areas() {
+        assert!(close(RegularPolygon::<3>::new(2.0).area_f64(), 3f64.sqrt()));
+        let hexagon = RegularPolygon::<6>::new(2.0);
+        assert!(close(hexagon.area_f64(), 6.0 * 3f64.sqrt()));
+        assert_eq!(hexagon.area(), 10);
+        assert_eq!(hexagon.side(), 2.0);
+    }
+
+    #[test]
+    fn the_side_count_is_part_of_the_type() {
+        assert_eq!(<RegularPolygon<5> as ShapeInfo>::SIDES, 5);
+        assert_eq!(<RegularPolygon<12> as ShapeInfo>::NAME, "regular polygon");
+        assert_eq!(RegularPolygon::<12>::new(1.5).perimeter(), 18.0);
+    }
+
+    #[test]
+    fn dodecagon_area() {
+        //tan(15 degrees) is 2 - sqrt(3), so 12 / (4 * tan(15 degrees)) is 6 + 3 * sqrt(3).
+        let dodecagon = RegularPolygon::<12>::new(1.0);
+        assert!(close(dodecagon.area_f64(), 6.0 + 3.0 * 3f64.sqrt()));
+        assert!(close(RegularPolygon::<12>::new(2.0).area_f64(), 4.0 * dodecagon.area_f64()));
+        assert_eq!(dodecagon.area(), 11);
+    }
+
+    //With more and more sides the polygon gets closer to the circle around it.
+    #[test]
+    fn many_sides_approach_a_circle() {
+        let n = 1000;
+        let polygon = RegularPolygon::<1000>::new(1.0);
+        let radius = 1.0 / (2.0 * (std::f64::consts::PI / n as f64).sin());
+        let circle = std::f64::consts::PI * radius * radius;
+        assert!((polygon.area_f64() - circle).abs() / circle < 1e-4);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::spatial::Point;
+use crate::traits::{Circle, Shape, Square, Triangle};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub type Color = (u8, u8, u8);
+
+//Where to put a shape, counted in pixels from the top left corner of the image.
+pub type Offset = (usize, usize);
+
+pub const BACKGROUND: Color = (255, 255, 255);
+
+//A binary P6 PPM, which is a short text header and then three bytes for every pixel going
+// across each row from the top. The image starts out white and the shapes are filled in order,
+// so later shapes are drawn over earlier ones. The offset is the top left corner of the shape's
+// bounding box and anything hanging off the edge of the image is clipped. Only shapes that are
+// Spatial can be drawn, anything else (like a circle) is skipped.
+pub fn render_ppm(
+    shapes: &[(Box<dyn Shape>, Color, Offset)],
+    width: usize,
+    height: usize,
+) -> Vec<u8> {
+    let mut pixels = vec![BACKGROUND; width * height];
+    for (shape, color, (left, top)) in shapes {
+        let Some(spatial) = shape.as_spatial() else {
+            continue;
+        };
+        let bounds = spatial.bounding_box();
+        let shape_width = bounds.width.max(0) as usize;
+        let shape_height = bounds.height.max(0) as usize;
+        for row in *top..(top + shape_height).min(height) {
+            for column in *left..(left + shape_width).min(width) {
+                //The middle of the pixel, flipped so y goes up from the bottom of the shape the
+                // same way it does in the spatial module.
+                let p = Point {
+                    x: (column - left) as f64 + 0.5,
+                    y: (top + shape_height - row) as f64 - 0.5,
+                };
+                if spatial.contains(&p) {
+                    pixels[row * width + column] = *color;
+                }
+            }
+        }
+    }
+
+    let mut buffer = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for (r, g, b) in pixels {
+        buffer.extend([r, g, b]);
+    }
+    buffer
+}
+
+pub fn write_ppm(
+    path: &Path,
+    shapes: &[(Box<dyn Shape>, Color, Offset)],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    fs::write(path, render_ppm(shapes, width, height))
+}
+
+//The picture `--render` draws. The circle is there to show that it gets skipped.
+pub fn demo_scene() -> Vec<(Box<dyn Shape>, Color, Offset)> {
+    vec![
+        (Box::new(Square { height: 10 }), (200, 40, 40), (2, 4)),
+        (Box::new(Triangle { base: 5, height: 10 }), (40, 40, 200), (14, 4)),
+        (Box::new(Circle { radius: 3.0 }), (40, 200, 40), (22, 2)),
+        (Box::new(Square { height: 8 }), (240, 200, 0), (26, 10)),
+    ]
+}
+
+pub const DEMO_WIDTH: usize = 32;
+pub const DEMO_HEIGHT: usize = 16;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color = (255, 0, 0);
+    const BLUE: Color = (0, 0, 255);
+
+    fn header(width: usize, height: usize) -> Vec<u8> {
+        format!("P6\n{width} {height}\n255\n").into_bytes()
+    }
+
+    //Splits the image back into its header and one color per pixel, checking the sizes match.
+    fn pixels(image: &[u8], width: usize, height: usize) -> Vec<Color> {
+        let (head, body) = image.split_at(header(width, height).len());
+        assert_eq!(head, header(width, height));
+        assert_eq!(body.len(), width * height * 3);
+        body.chunks(3).map(|rgb| (rgb[0], rgb[1], rgb[2])).collect()
+    }
+
+    fn square(height: isize, color: Color, offset: Offset) -> (Box<dyn Shape>, Color, Offset) {
+        (Box::new(Square { height }), color, offset)
+    }
+
+    #[test]
+    fn empty_scene_is_all_background() {
+        let image = render_ppm(&[], 3, 2);
+        assert!(image.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(pixels(&image, 3, 2), vec![BACKGROUND; 6]);
+    }
+
+    #[test]
+    fn zero_sized_image_is_just_the_header() {
+        assert_eq!(render_ppm(&[square(2, RED, (0, 0))], 0, 0), header(0, 0));
+    }
+
+    #[test]
+    fn square_lands_at_its_offset() {
+        let image = render_ppm(&[square(2, RED, (1, 1))], 4, 3);
+        let w = BACKGROUND;
+        //Three rows of four pixels, top row first.
+        let expected = [[w, w, w, w], [w, RED, RED, w], [w, RED, RED, w]].concat();
+        assert_eq!(pixels(&image, 4, 3), expected);
+    }
+
+    #[test]
+    fn triangle_has_its_right_angle_at_the_bottom_left() {
+        let triangle: Box<dyn Shape> = Box::new(Triangle { base: 2, height: 2 });
+        let image = render_ppm(&[(triangle, BLUE, (0, 0))], 2, 2);
+        assert_eq!(pixels(&image, 2, 2), vec![BLUE, BACKGROUND, BLUE, BLUE]);
+    }
+
+    #[test]
+    fn later_shapes_draw_over_earlier_ones() {
+        let image = render_ppm(&[square(2, RED, (0, 0)), square(1, BLUE, (1, 0))], 2, 2);
+        assert_eq!(pixels(&image, 2, 2), vec![RED, BLUE, RED, RED]);
+    }
+
+    #[test]
+    fn shapes_off_the_edge_are_clipped() {
+        let shapes = [square(5, RED, (2, 1)), square(3, BLUE, (10, 10))];
+        let image = render_ppm(&shapes, 3, 2);
+        let w = BACKGROUND;
+        assert_eq!(pixels(&image, 3, 2), vec![w, w, w, w, w, RED]);
+    }
+
+    #[test]
+    fn shapes_that_are_not_spatial_are_skipped() {
+        let circle: Box<dyn Shape> = Box::new(Circle { radius: 5.0 });
+        let image = render_ppm(&[(circle, RED, (0, 0))], 2, 2);
+        assert_eq!(pixels(&image, 2, 2), vec![BACKGROUND; 4]);
+    }
+
+    #[test]
+    fn demo_scene_fits_the_demo_size() {
+        let image = render_ppm(&demo_scene(), DEMO_WIDTH, DEMO_HEIGHT);
+        let colors = pixels(&image, DEMO_WIDTH, DEMO_HEIGHT);
+        assert_eq!(colors[4 * DEMO_WIDTH + 2], (200, 40, 40));
+        assert_eq!(colors.iter().filter(|&&c| c == (40, 200, 40)).count(), 0);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::rng::Lcg;
+use crate::traits::{Shape, Square, Triangle};
+use std::io::{self, BufRead, Write};
+
+pub const QUIZ_ROUNDS: usize = 5;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct QuizResult {
+    pub correct: usize,
+    pub incorrect: usize,
+    pub skipped: usize,
+}
+
+impl QuizResult {
+    pub fn answered(&self) -> usize {
+        self.correct + self.incorrect + self.skipped
+    }
+}
+
+//Every round asks for the area of a random square or triangle. Anything that isn't a number or
+// `skip` asks again instead of counting as a wrong answer. Running out of input ends the quiz
+// early with whatever the score was at that point.
+pub fn run_quiz(
+    rng_seed: u64,
+    rounds: usize,
+    input: impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<QuizResult> {
+    let mut rng = Lcg::new(rng_seed);
+    let mut result = QuizResult::default();
+    let mut lines = input.lines();
+
+    'rounds: for round in 1..=rounds {
+        let (question, shape): (String, Box<dyn Shape>) = if rng.next_u32().is_multiple_of(2) {
+            let height = rng.next_range(1, 12);
+            (format!("a square with height {height}"), Box::new(Square { height }))
+        } else {
+            let (base, height) = (rng.next_range(1, 12), rng.next_range(1, 12));
+            (
+                format!("a triangle with base {base} and height {height} (halves round down)"),
+                Box::new(Triangle { base, height }),
+            )
+        };
+        let area = shape.area();
+        writeln!(output, "round {round}: what is the area of {question}?")?;
+
+        loop {
+            let Some(line) = lines.next() else {
+                break 'rounds;
+            };
+            let answer = line?;
+            let answer = answer.trim();
+            if answer == "skip" {
+                result.skipped += 1;
+                writeln!(output, "skipped, the area was {area}")?;
+            } else if let Ok(guess) = answer.parse::<isize>() {
+                if guess == area {
+                    result.correct += 1;
+                    writeln!(output, "correct!")?;
+                } else {
+                    result.incorrect += 1;
+                    writeln!(output, "wrong, the area was {area}")?;
+                }
+            } else {
+                writeln!(output, "'{answer}' isn't a number, try again or type skip")?;
+                continue;
+            }
+            writeln!(output, "score: {}/{}", result.correct, result.answered())?;
+            break;
+        }
+    }
+
+    writeln!(output, "final score: {}/{}", result.correct, rounds)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 7;
+
+    fn play(seed: u64, rounds: usize, script: &str) -> (QuizResult, String) {
+        let mut output = Vec::new();
+        let result = run_quiz(seed, rounds, script.as_bytes(), &mut output).unwrap();
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    fn questions(output: &str) -> Vec<&str> {
+        output.lines().filter(|line| line.starts_with("round ")).collect()
+    }
+
+    //Skipping every round makes the quiz print each answer, so a test can find out what to type
+    // without copying the rng by hand.
+    fn answers(seed: u64, rounds: usize) -> Vec<isize> {
+        let (_, output) = play(seed, rounds, &"skip\n".repeat(rounds));
+        output
+            .lines()
+            .filter_map(|line| line.strip_prefix("skipped, the area was "))
+            .map(|area| area.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_asks_the_same_questions() {
+        let script = "skip\n".repeat(QUIZ_ROUNDS);
+        let (_, first) = play(SEED, QUIZ_ROUNDS, &script);
+        let (_, second) = play(SEED, QUIZ_ROUNDS, &script);
+        assert_eq!(questions(&first).len(), QUIZ_ROUNDS);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn right_answers_all_score() {
+        let script: String = answers(SEED, 3).iter().map(|area| format!("{area}\n")).collect();
+        let (result, output) = play(SEED, 3, &script);
+        assert_eq!(result, QuizResult { correct: 3, incorrect: 0, skipped: 0 });
+        assert!(output.ends_with("score: 3/3\nfinal score: 3/3\n"));
+    }
+
+    #[test]
+    fn wrong_answers_count_and_show_the_area() {
+        let areas = answers(SEED, 2);
+        let script = format!("{}\n{}\n", areas[0], areas[1] + 1);
+        let (result, output) = play(SEED, 2, &script);
+        assert_eq!(result, QuizResult { correct: 1, incorrect: 1, skipped: 0 });
+        assert!(output.contains(&format!("wrong, the area was {}\nscore: 1/2\n", areas[1])));
+    }
+
+    #[test]
+    fn non_numbers_ask_again_without_counting() {
+        let areas = answers(SEED, 1);
+        let script = format!("ten\n\n{}\n", areas[0]);
+        let (result, output) = play(SEED, 1, &script);
+        assert_eq!(result, QuizResult { correct: 1, incorrect: 0, skipped: 0 });
+        assert!(output.contains("'ten' isn't a number, try again or type skip\n"));
+        assert!(output.contains("'' isn't a number, try again or type skip\n"));
+        assert_eq!(questions(&output).len(), 1);
+    }
+
+    #[test]
+    fn running_out_of_input_ends_early() {
+        let (result, output) = play(SEED, QUIZ_ROUNDS, "skip\n");
+        assert_eq!(result, QuizResult { correct: 0, incorrect: 0, skipped: 1 });
+        assert_eq!(questions(&output).len(), 2);
+        assert!(output.ends_with(&format!("final score: 0/{QUIZ_ROUNDS}\n")));
+    }
+}
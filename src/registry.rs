@@ -0,0 +1,110 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+use std::collections::HashMap;
+
+//Shapes looked up by name. The map owns the boxes, and get hands back a reference to what is
+// inside one of them, which is only good for as long as the registry is borrowed.
+pub struct ShapeRegistry {
+    shapes: HashMap<String, Box<dyn Shape>>,
+}
+
+impl ShapeRegistry {
+    pub fn new() -> Self {
+        ShapeRegistry { shapes: HashMap::new() }
+    }
+
+    //Registering a name that is already taken replaces the old shape and hands it back.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        shape: Box<dyn Shape>,
+    ) -> Option<Box<dyn Shape>> {
+        self.shapes.insert(name.into(), shape)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Shape> {
+        self.shapes.get(name).map(|shape| shape.as_ref())
+    }
+
+    pub fn area_of(&self, name: &str) -> Option<isize> {
+        self.get(name).map(|shape| shape.area())
+    }
+
+    //A HashMap has no order of its own, so the names are sorted before handing them out.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.shapes.keys().map(|name| name.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+}
+
+impl Default for ShapeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut registry = ShapeRegistry::new();
+    registry.register("triangle", Box::new(Triangle { base: 5, height: 10 }));
+    registry.register("square", Box::new(Square { height: 10 }));
+
+    out.line(format!("registered: {:?}", registry.names()));
+    out.line(format!("area of square: {:?}", registry.area_of("square")));
+    out.line(format!("area of triangle: {:?}", registry.area_of("triangle")));
+    out.line(format!("area of circle: {:?}", registry.area_of("circle")));
+
+    let replaced = registry.register("square", Box::new(Square { height: 2 }));
+    out.line(format!(
+        "replaced a square of area {:?}, now {:?}",
+        replaced.map(|shape| shape.area()),
+        registry.area_of("square")
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> ShapeRegistry {
+        let mut registry = ShapeRegistry::default();
+        registry.register("triangle", Box::new(Triangle { base: 5, height: 10 }));
+        registry.register("square", Box::new(Square { height: 10 }));
+        registry
+    }
+
+    #[test]
+    fn looks_shapes_up_by_name() {
+        let registry = registry();
+        assert_eq!(registry.area_of("square"), Some(100));
+        assert_eq!(registry.get("triangle").map(|shape| shape.area()), Some(25));
+        assert_eq!(registry.area_of("circle"), None);
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn names_come_out_sorted() {
+        let mut registry = registry();
+        registry.register("circle", Box::new(Square { height: 1 }));
+        assert_eq!(registry.names(), ["circle", "square", "triangle"]);
+        assert!(ShapeRegistry::new().names().is_empty());
+    }
+
+    #[test]
+    fn registering_a_taken_name_hands_back_the_old_shape() {
+        let mut registry = registry();
+        let old = registry.register("square", Box::new(Square { height: 2 }));
+        assert_eq!(old.map(|shape| shape.area()), Some(100));
+        assert_eq!(registry.area_of("square"), Some(4));
+        assert_eq!(registry.len(), 2);
+        assert!(registry.register(String::from("new"), Box::new(Square { height: 1 })).is_none());
+    }
+}
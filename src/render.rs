@@ -0,0 +1,123 @@
+use crate::output::Output;
+use crate::traits::{Circle, Square, Triangle};
+
+//Anything bigger than this gets clamped down so a huge shape can't flood the terminal.
+pub const MAX_RENDER_SIZE: isize = 20;
+
+//Kept separate from Shape so that Shape doesn't change. There is no default here, every shape
+// has to work out its own drawing.
+pub trait Render {
+    fn render_ascii(&self) -> String;
+}
+
+fn clamp_size(size: isize) -> isize {
+    size.clamp(0, MAX_RENDER_SIZE)
+}
+
+//A shape with no height (or a negative one) renders as an empty string.
+impl Render for Square {
+    fn render_ascii(&self) -> String {
+        let size = clamp_size(self.height) as usize;
+        vec!["#".repeat(size); size].join("\n")
+    }
+}
+
+//The right angle is in the bottom left corner, so each row is a little wider than the one above
+// it until the last row is the full base.
+impl Render for Triangle {
+    fn render_ascii(&self) -> String {
+        let base = clamp_size(self.base);
+        let height = clamp_size(self.height);
+        if base == 0 {
+            return String::new();
+        }
+        (1..=height)
+            .map(|row| {
+                let width = ((base * row) as f64 / height as f64).round().max(1.0) as usize;
+                "#".repeat(width)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+//A cell is filled in when its middle is inside the circle.
+impl Render for Circle {
+    fn render_ascii(&self) -> String {
+        let diameter = clamp_size((self.radius * 2.0).round() as isize);
+        let radius = diameter as f64 / 2.0;
+        (0..diameter)
+            .map(|row| {
+                (0..diameter)
+                    .map(|column| {
+                        let x = column as f64 + 0.5 - radius;
+                        let y = row as f64 + 0.5 - radius;
+                        if x * x + y * y <= radius * radius {
+                            '#'
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    out.line(Square { height: 4 }.render_ascii());
+    out.line(Triangle { base: 4, height: 4 }.render_ascii());
+    out.line(Circle { radius: 3.0 }.render_ascii());
+    out.line(format!(
+        "a square of height 1000 is clamped to {} rows",
+        Square { height: 1000 }.render_ascii().lines().count()
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_is_a_block_of_hashes() {
+        assert_eq!(Square { height: 2 }.render_ascii(), "##\n##");
+        assert_eq!(Square { height: 0 }.render_ascii(), "");
+        assert_eq!(Square { height: -3 }.render_ascii(), "");
+    }
+
+    #[test]
+    fn triangle_rows_grow_to_the_full_base() {
+        assert_eq!(Triangle { base: 4, height: 4 }.render_ascii(), "#\n##\n###\n####");
+        //Every row gets at least one #, even when the slope says less.
+        assert_eq!(Triangle { base: 2, height: 4 }.render_ascii(), "#\n#\n##\n##");
+        assert_eq!(Triangle { base: 0, height: 4 }.render_ascii(), "");
+        assert_eq!(Triangle { base: 4, height: 0 }.render_ascii(), "");
+    }
+
+    #[test]
+    fn circle_fills_the_cells_whose_middle_is_inside() {
+        assert_eq!(Circle { radius: 1.0 }.render_ascii(), "##\n##");
+        assert_eq!(Circle { radius: 0.0 }.render_ascii(), "");
+        let rows: Vec<String> = Circle { radius: 3.0 }
+            .render_ascii()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[0], " ####");
+        assert_eq!(rows[2], "######");
+    }
+
+    #[test]
+    fn huge_shapes_are_clamped() {
+        let size = MAX_RENDER_SIZE as usize;
+        let square = Square { height: 1000 }.render_ascii();
+        assert_eq!(square.lines().count(), size);
+        assert!(square.lines().all(|line| line.len() == size));
+        assert_eq!(Triangle { base: 1000, height: 1000 }.render_ascii().lines().count(), size);
+        assert_eq!(Circle { radius: 1000.0 }.render_ascii().lines().count(), size);
+    }
+}
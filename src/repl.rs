@@ -0,0 +1,124 @@
+use crate::error::ChapterError;
+use crate::parse::ParseShapeError;
+use crate::traits::{Shape, Square, Triangle};
+use std::io::{self, BufRead, Write};
+
+//Reads one command per line until `quit` or the end of the input:
+// square <height>          adds a square and prints its area
+// triangle <base> <height> adds a triangle and prints its area
+// total                    prints the sum of every area added so far
+// quit                     stops reading
+//A bad command prints an error line and the session keeps going. Only failing to read or write
+// ends it early.
+pub fn repl(input: impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["quit"] => break,
+            ["total"] => {
+                let total: isize = shapes.iter().map(|shape| shape.area()).sum();
+                writeln!(output, "total: {total}")?;
+            }
+            ["square", height] => match make_square(height) {
+                Ok(square) => add_shape(&mut shapes, square, output)?,
+                Err(e) => writeln!(output, "error: {e}")?,
+            },
+            ["triangle", base, height] => match make_triangle(base, height) {
+                Ok(triangle) => add_shape(&mut shapes, triangle, output)?,
+                Err(e) => writeln!(output, "error: {e}")?,
+            },
+            ["square", ..] => writeln!(output, "error: usage: square <height>")?,
+            ["triangle", ..] => writeln!(output, "error: usage: triangle <base> <height>")?,
+            [command, ..] => writeln!(output, "error: unknown command '{command}'")?,
+        }
+    }
+    Ok(())
+}
+
+fn parse_number(word: &str) -> Result<isize, ParseShapeError> {
+    word.parse().map_err(|_| ParseShapeError::InvalidNumber(word.to_string()))
+}
+
+fn make_square(height: &str) -> Result<Box<dyn Shape>, ChapterError> {
+    Ok(Box::new(Square::new(parse_number(height)?)?))
+}
+
+fn make_triangle(base: &str, height: &str) -> Result<Box<dyn Shape>, ChapterError> {
+    Ok(Box::new(Triangle::new(parse_number(base)?, parse_number(height)?)?))
+}
+
+fn add_shape(
+    shapes: &mut Vec<Box<dyn Shape>>,
+    shape: Box<dyn Shape>,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(output, "area: {}", shape.area())?;
+    shapes.push(shape);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Runs a whole script through the repl and hands back what it printed.
+    fn transcript(script: &str) -> String {
+        let mut output = Vec::new();
+        repl(script.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn adds_shapes_and_totals_them() {
+        let output = transcript("square 3\ntriangle 4 5\ntotal\n");
+        assert_eq!(output, "area: 9\narea: 10\ntotal: 19\n");
+    }
+
+    #[test]
+    fn total_of_nothing_is_zero() {
+        assert_eq!(transcript("total\n"), "total: 0\n");
+    }
+
+    #[test]
+    fn bad_commands_print_an_error_and_keep_going() {
+        let script = "\
+square x
+square -2
+square 1 2
+triangle 3
+circle 4
+square 2
+total
+";
+        let expected = "\
+error: could not parse shape: 'x' is not a number
+error: invalid shape: height must be positive, got -2
+error: usage: square <height>
+error: usage: triangle <base> <height>
+error: unknown command 'circle'
+area: 4
+total: 4
+";
+        assert_eq!(transcript(script), expected);
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        assert_eq!(transcript("\n   \nsquare 2\n\n"), "area: 4\n");
+    }
+
+    #[test]
+    fn quit_stops_before_the_rest_of_the_script() {
+        assert_eq!(transcript("square 2\nquit\nsquare 3\ntotal\n"), "area: 4\n");
+    }
+
+    #[test]
+    fn end_of_input_ends_the_session() {
+        //No trailing newline and no quit, the last line still counts.
+        assert_eq!(transcript("square 5"), "area: 25\n");
+        assert_eq!(transcript(""), "");
+    }
+}
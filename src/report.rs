@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+//What a section did when it ran. Each demo in a section adds its name, and anything worth
+// looking at afterwards (like an area that was worked out) gets recorded as a value.
+#[derive(Debug)]
+pub struct SectionReport {
+    pub section: &'static str,
+    pub demonstrations: Vec<&'static str>,
+    pub values: Vec<(&'static str, String)>,
+}
+
+impl SectionReport {
+    pub fn new(section: &'static str) -> Self {
+        SectionReport { section, demonstrations: Vec::new(), values: Vec::new() }
+    }
+
+    pub fn demo(&mut self, name: &'static str) {
+        self.demonstrations.push(name);
+    }
+
+    pub fn record(&mut self, name: &'static str, value: impl Display) {
+        self.values.push((name, value.to_string()));
+    }
+
+    pub fn demonstration_count(&self) -> usize {
+        self.demonstrations.len()
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(value_name, _)| *value_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+//One row per section with the number of demos run and the recorded values.
+pub fn summary_table(reports: &[SectionReport]) -> String {
+    let width = reports.iter().map(|report| report.section.len()).max().unwrap_or(0).max(7);
+    let mut lines = vec![format!("{:<width$}  demos  values", "section")];
+    for report in reports {
+        let values: Vec<String> =
+            report.values.iter().map(|(name, value)| format!("{name}={value}")).collect();
+        lines.push(format!(
+            "{:<width$}  {:>5}  {}",
+            report.section,
+            report.demonstration_count(),
+            values.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(section: &'static str, demos: &[&'static str]) -> SectionReport {
+        let mut report = SectionReport::new(section);
+        demos.iter().for_each(|&demo| report.demo(demo));
+        report
+    }
+
+    #[test]
+    fn records_demos_and_values() {
+        let mut report = report("traits", &["area", "checked area"]);
+        report.record("total area", 125);
+        report.record("name", "house");
+        assert_eq!(report.demonstration_count(), 2);
+        assert_eq!(report.value("total area"), Some("125"));
+        assert_eq!(report.value("name"), Some("house"));
+        assert_eq!(report.value("missing"), None);
+    }
+
+    #[test]
+    fn value_finds_the_first_of_a_repeated_name() {
+        let mut report = report("generics", &[]);
+        report.record("calls", 1);
+        report.record("calls", 2);
+        assert_eq!(report.value("calls"), Some("1"));
+    }
+
+    #[test]
+    fn summary_of_nothing_is_just_the_header() {
+        assert_eq!(summary_table(&[]), "section  demos  values");
+    }
+
+    #[test]
+    fn summary_lines_up_the_columns() {
+        let mut lifetimes = report("lifetimes", &["a", "b", "c"]);
+        lifetimes.record("result", 2);
+        lifetimes.record("longest", "xyz");
+        let table = summary_table(&[report("traits", &["a"]), lifetimes]);
+        let expected = [
+            "section    demos  values",
+            //No values still leaves the two spaces before the column.
+            "traits         1  ",
+            "lifetimes      3  result=2, longest=xyz",
+        ];
+        assert_eq!(table, expected.join("\n"));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+
+//A linear congruential generator, the simplest random numbers there are. Each step is
+// state * 6364136223846793005 + 1442695040888963407, wrapping around at 2^64. Those are the
+// constants Knuth uses for MMIX. They only use arithmetic that means the same thing everywhere,
+// so the same seed gives the same numbers on every machine and every run, which is what lets a
+// quiz be replayed. None of this is good enough for anything that needs to be unpredictable.
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg { state: seed }
+    }
+
+    //The low bits of an LCG repeat quickly (the lowest one just flips back and forth), so only
+    // the top 31 bits of the state are handed out. That makes the range 0 to 2^31 - 1, and the
+    // top bit of the u32 is always 0.
+    pub fn next_u32(&mut self) -> u32 {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.state >> 33) as u32
+    }
+
+    //Somewhere from lo to hi, including both. Taking the remainder favours the smaller values
+    // very slightly, which doesn't matter for picking the side of a square.
+    pub fn next_range(&mut self, lo: isize, hi: isize) -> isize {
+        assert!(lo <= hi, "the range {lo}..={hi} is empty");
+        let span = (hi - lo) as u64 + 1;
+        lo + (u64::from(self.next_u32()) % span) as isize
+    }
+}
+
+//Squares and triangles in about equal numbers, with every side from 1 to 100.
+pub fn random_shapes(rng: &mut Lcg, n: usize) -> Vec<Box<dyn Shape>> {
+    (0..n)
+        .map(|_| -> Box<dyn Shape> {
+            if rng.next_u32().is_multiple_of(2) {
+                Box::new(Square { height: rng.next_range(1, 100) })
+            } else {
+                let base = rng.next_range(1, 100);
+                Box::new(Triangle { base, height: rng.next_range(1, 100) })
+            }
+        })
+        .collect()
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut rng = Lcg::new(42);
+    let first: Vec<u32> = (0..10).map(|_| rng.next_u32()).collect();
+    out.line(format!("first ten from seed 42: {:?}", first));
+
+    let samples: Vec<isize> = (0..1000).map(|_| rng.next_range(1, 6)).collect();
+    out.line(format!(
+        "1000 dice rolls from {:?} to {:?}",
+        samples.iter().min(),
+        samples.iter().max()
+    ));
+
+    let areas = |seed| -> Vec<isize> {
+        random_shapes(&mut Lcg::new(seed), 5).iter().map(|shape| shape.area()).collect()
+    };
+    out.line(format!("random areas from seed 7: {:?}", areas(7)));
+    out.line(format!("same seed, same areas? {}", areas(7) == areas(7)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_42_gives_the_same_numbers_as_always() {
+        let mut rng = Lcg::new(42);
+        let first: Vec<u32> = (0..10).map(|_| rng.next_u32()).collect();
+        let expected = [
+            1220265334, 484179026, 886563538, 1353769503, 1460606294, 56326156, 46730969,
+            327394710, 1017823166, 53256125,
+        ];
+        assert_eq!(first, expected);
+    }
+
+    #[test]
+    fn next_u32_stays_below_2_to_the_31() {
+        let mut rng = Lcg::new(0);
+        assert!((0..10_000).all(|_| rng.next_u32() < 1 << 31));
+    }
+
+    #[test]
+    fn next_range_includes_both_ends() {
+        let mut rng = Lcg::new(42);
+        let rolls: Vec<isize> = (0..1000).map(|_| rng.next_range(1, 6)).collect();
+        assert_eq!(rolls.iter().min(), Some(&1));
+        assert_eq!(rolls.iter().max(), Some(&6));
+        assert_eq!(Lcg::new(3).next_range(-2, -2), -2);
+    }
+
+    #[test]
+    #[should_panic(expected = "the range 3..=1 is empty")]
+    fn next_range_rejects_an_empty_range() {
+        Lcg::new(1).next_range(3, 1);
+    }
+
+    #[test]
+    fn random_shapes_only_depend_on_the_seed() {
+        let areas = |seed| -> Vec<isize> {
+            random_shapes(&mut Lcg::new(seed), 5).iter().map(|shape| shape.area()).collect()
+        };
+        assert_eq!(areas(7), [1024, 1702, 2112, 4176, 1336]);
+        assert_eq!(areas(7), areas(7));
+        assert_ne!(areas(7), areas(8));
+        assert!(random_shapes(&mut Lcg::new(7), 0).is_empty());
+    }
+}
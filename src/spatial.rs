@@ -0,0 +1,153 @@
+use crate::output::Output;
+use crate::traits::{Square, Triangle};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+//An axis aligned rectangle. (x, y) is the bottom left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: isize,
+    pub y: isize,
+    pub width: isize,
+    pub height: isize,
+}
+
+impl Rect {
+    pub fn area(&self) -> isize {
+        self.width * self.height
+    }
+
+    pub fn moved_by(&self, dx: isize, dy: isize) -> Rect {
+        Rect { x: self.x + dx, y: self.y + dy, ..*self }
+    }
+
+    //Only the insides count, so two rectangles that share an edge or a corner don't overlap.
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+//Every shape sits with its bottom left corner on the origin. A point exactly on an edge or a
+// corner counts as being inside the shape.
+pub trait Spatial {
+    fn contains(&self, p: &Point) -> bool;
+    fn bounding_box(&self) -> Rect;
+}
+
+impl Spatial for Square {
+    fn contains(&self, p: &Point) -> bool {
+        let side = self.height as f64;
+        (0.0..=side).contains(&p.x) && (0.0..=side).contains(&p.y)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        Rect { x: 0, y: 0, width: self.height, height: self.height }
+    }
+}
+
+//The base runs along the x axis and the height up the y axis, so the hypotenuse goes from
+// (base, 0) to (0, height).
+impl Spatial for Triangle {
+    fn contains(&self, p: &Point) -> bool {
+        let base = self.base as f64;
+        let height = self.height as f64;
+        p.x >= 0.0 && p.y >= 0.0 && p.x * height + p.y * base <= base * height
+    }
+
+    fn bounding_box(&self) -> Rect {
+        Rect { x: 0, y: 0, width: self.base, height: self.height }
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let square = Square { height: 10 };
+    let triangle = Triangle { base: 5, height: 10 };
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.5, y: 5.0 },
+        Point { x: 4.0, y: 8.0 },
+        Point { x: 10.0, y: 10.0 },
+        Point { x: -1.0, y: 3.0 },
+    ];
+
+    out.line(format!(
+        "square bounding box: {:?} triangle bounding box: {:?}",
+        square.bounding_box(),
+        triangle.bounding_box()
+    ));
+    for p in points {
+        out.line(format!(
+            "({}, {}) in square? {} in triangle? {}",
+            p.x,
+            p.y,
+            square.contains(&p),
+            triangle.contains(&p)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: isize, y: isize, width: isize, height: isize) -> Rect {
+        Rect { x, y, width, height }
+    }
+
+    fn point(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn rect_area_and_moving() {
+        assert_eq!(rect(0, 0, 3, 4).area(), 12);
+        assert_eq!(rect(1, 2, 3, 4).moved_by(-1, 5), rect(0, 7, 3, 4));
+    }
+
+    #[test]
+    fn sharing_an_edge_or_corner_is_not_overlapping() {
+        let a = rect(0, 0, 10, 10);
+        assert!(a.overlaps(&rect(5, 5, 10, 10)));
+        assert!(a.overlaps(&rect(2, 2, 1, 1)));
+        assert!(!a.overlaps(&rect(10, 0, 5, 5)));
+        assert!(!a.overlaps(&rect(10, 10, 5, 5)));
+        assert!(!a.overlaps(&rect(0, -5, 10, 5)));
+    }
+
+    #[test]
+    fn square_contains_its_edges() {
+        let square = Square { height: 10 };
+        assert!(square.contains(&point(0.0, 0.0)));
+        assert!(square.contains(&point(10.0, 10.0)));
+        assert!(square.contains(&point(5.0, 2.5)));
+        assert!(!square.contains(&point(10.1, 5.0)));
+        assert!(!square.contains(&point(-1.0, 3.0)));
+    }
+
+    #[test]
+    fn triangle_is_cut_off_by_the_hypotenuse() {
+        let triangle = Triangle { base: 5, height: 10 };
+        assert!(triangle.contains(&point(0.0, 0.0)));
+        assert!(triangle.contains(&point(5.0, 0.0)));
+        assert!(triangle.contains(&point(0.0, 10.0)));
+        assert!(triangle.contains(&point(2.5, 0.0)) && triangle.contains(&point(0.0, 7.0)));
+        assert!(triangle.contains(&point(2.5, 5.0)));
+        assert!(!triangle.contains(&point(5.0, 0.1)) && !triangle.contains(&point(0.1, 10.0)));
+        assert!(!triangle.contains(&point(5.0, 10.0)));
+        assert!(!triangle.contains(&point(4.0, 8.0)));
+        assert!(!triangle.contains(&point(1.0, -0.5)));
+    }
+
+    #[test]
+    fn bounding_boxes_sit_on_the_origin() {
+        assert_eq!(Square { height: 4 }.bounding_box(), rect(0, 0, 4, 4));
+        assert_eq!(Triangle { base: 5, height: 10 }.bounding_box(), rect(0, 0, 5, 10));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::output::Output;
+use crate::traits::{Shape, Square, Triangle};
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+//Which shape a table is for. A triangle needs a base as well, and it stays the same while the
+// height changes from row to row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeKind {
+    Square,
+    Triangle { base: isize },
+}
+
+impl ShapeKind {
+    fn area(&self, dim: isize) -> isize {
+        match *self {
+            ShapeKind::Square => Square { height: dim }.area(),
+            ShapeKind::Triangle { base } => Triangle { base, height: dim }.area(),
+        }
+    }
+}
+
+//A negative side doesn't make a shape, so a table that would need one is refused as a whole
+// instead of quietly leaving rows out.
+#[derive(Debug, PartialEq)]
+pub enum TableError {
+    NegativeDimension(isize),
+    NegativeBase(isize),
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TableError::NegativeDimension(dim) => {
+                write!(f, "dimensions can't be negative, the range starts at {dim}")
+            }
+            TableError::NegativeBase(base) => write!(f, "base can't be negative, got {base}"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+//One (dimension, area) row for every size in the range, zero included. A range that is empty,
+// like 5..=1, gives no rows at all, even if it starts below zero.
+pub fn area_table(
+    kind: ShapeKind,
+    dims: RangeInclusive<isize>,
+) -> Result<Vec<(isize, isize)>, TableError> {
+    if let ShapeKind::Triangle { base } = kind {
+        if base < 0 {
+            return Err(TableError::NegativeBase(base));
+        }
+    }
+    if !dims.is_empty() && *dims.start() < 0 {
+        return Err(TableError::NegativeDimension(*dims.start()));
+    }
+    Ok(dims.map(|dim| (dim, kind.area(dim))).collect())
+}
+
+fn column_width(header: &str, values: impl Iterator<Item = isize>) -> usize {
+    values.map(|value| value.to_string().len()).fold(header.len(), usize::max)
+}
+
+//Both columns are right aligned and as wide as their widest entry, header included.
+pub fn format_table(rows: &[(isize, isize)], headers: (&str, &str)) -> String {
+    let left = column_width(headers.0, rows.iter().map(|row| row.0));
+    let right = column_width(headers.1, rows.iter().map(|row| row.1));
+
+    let mut lines = Vec::from([
+        format!("{:>left$} | {:>right$}", headers.0, headers.1),
+        format!("{}-+-{}", "-".repeat(left), "-".repeat(right)),
+    ]);
+    for (dim, area) in rows {
+        lines.push(format!("{:>left$} | {:>right$}", dim, area));
+    }
+    lines.join("\n")
+}
+
+fn print_table(out: &mut dyn Output, table: &str) {
+    for line in table.lines() {
+        out.line(line.to_string());
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let (from, to) = (5, 1);
+    let tables = [
+        (ShapeKind::Square, 1..=10, ("side", "area")),
+        (ShapeKind::Triangle { base: 4 }, 1..=10, ("height", "area")),
+        (ShapeKind::Square, from..=to, ("side", "area")),
+        (ShapeKind::Square, -2..=1, ("side", "area")),
+    ];
+    for (kind, dims, headers) in tables {
+        match area_table(kind, dims.clone()) {
+            Ok(rows) => print_table(out, &format_table(&rows, headers)),
+            Err(e) => out.line(format!("no table for {dims:?}: {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_cover_the_whole_range() {
+        let rows = area_table(ShapeKind::Square, 0..=3).unwrap();
+        assert_eq!(rows, [(0, 0), (1, 1), (2, 4), (3, 9)]);
+        let rows = area_table(ShapeKind::Triangle { base: 3 }, 1..=3).unwrap();
+        assert_eq!(rows, [(1, 1), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn negative_dimensions_are_an_error() {
+        let (from, to) = (-2, 1);
+        let rows = area_table(ShapeKind::Square, from..=to);
+        assert_eq!(rows, Err(TableError::NegativeDimension(-2)));
+        let kind = ShapeKind::Triangle { base: -4 };
+        assert_eq!(area_table(kind, 1..=2), Err(TableError::NegativeBase(-4)));
+    }
+
+    #[test]
+    fn empty_range_is_an_empty_table() {
+        let (from, to) = (5, 1);
+        assert_eq!(area_table(ShapeKind::Square, from..=to), Ok(Vec::new()));
+        let (from, to) = (-1, -5);
+        assert_eq!(area_table(ShapeKind::Square, from..=to), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn empty_table_is_just_the_header() {
+        assert_eq!(format_table(&[], ("side", "area")), "side | area\n-----+-----");
+    }
+
+    #[test]
+    fn columns_are_as_wide_as_the_widest_entry() {
+        let table = format_table(&[(1, 1), (100, 10000)], ("side", "area"));
+        let expected = "side |  area\n-----+------\n   1 |     1\n 100 | 10000";
+        assert_eq!(table, expected);
+        //Every line comes out the same width, so the bars line up.
+        assert!(table.lines().all(|line| line.len() == "side |  area".len()));
+    }
+
+    #[test]
+    fn header_wider_than_the_values_sets_the_width() {
+        let table = format_table(&[(2, 4)], ("height", "area"));
+        assert_eq!(table, "height | area\n-------+-----\n     2 |    4");
+    }
+}
@@ -0,0 +1,109 @@
+use crate::generics::{PrimaryColor, PrimaryColors};
+
+//Whether colors are on is decided once by main, when stdout is a terminal and --no-color
+// wasn't passed, and handed down to whatever prints. The default is off, so output captured
+// into a Vec or piped to a file stays plain.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Term {
+    color: bool,
+}
+
+impl Term {
+    pub fn new(color: bool) -> Self {
+        Term { color }
+    }
+
+    pub fn plain() -> Self {
+        Term { color: false }
+    }
+
+    pub fn color(&self) -> bool {
+        self.color
+    }
+
+    //With colors off s comes back unchanged.
+    pub fn colorize(&self, s: &str, color: Color) -> String {
+        if self.color {
+            paint(s, color)
+        } else {
+            s.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Yellow,
+    Blue,
+    Red,
+}
+
+impl Color {
+    //The ANSI codes for the normal (not bright) foreground colors.
+    fn code(self) -> u8 {
+        match self {
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Red => 31,
+        }
+    }
+}
+
+impl From<PrimaryColor> for Color {
+    fn from(color: PrimaryColor) -> Self {
+        match color {
+            PrimaryColor::Yellow => Color::Yellow,
+            PrimaryColor::Blue => Color::Blue,
+            PrimaryColor::Red => Color::Red,
+        }
+    }
+}
+
+//The payload doesn't matter, only which color it is.
+impl<T> From<&PrimaryColors<T>> for Color {
+    fn from(color: &PrimaryColors<T>) -> Self {
+        match color {
+            PrimaryColors::Yellow(_) => Color::Yellow,
+            PrimaryColors::Blue(_) => Color::Blue,
+            PrimaryColors::Red(_) => Color::Red,
+        }
+    }
+}
+
+//Wraps s in the escape code for the color and a reset at the end. Anything already in s,
+// including escape codes of its own, is left as it is.
+pub fn paint(s: &str, color: Color) -> String {
+    format!("\x1b[{}m{s}\x1b[0m", color.code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_on_wraps_in_escape_codes() {
+        let term = Term::new(true);
+        assert_eq!(term.colorize("100", Color::Yellow), "\x1b[33m100\x1b[0m");
+        assert_eq!(term.colorize("sky", Color::Blue), "\x1b[34msky\x1b[0m");
+        assert_eq!(term.colorize("", Color::Red), "\x1b[31m\x1b[0m");
+    }
+
+    #[test]
+    fn color_off_is_plain() {
+        assert_eq!(Term::plain().colorize("100", Color::Yellow), "100");
+        assert_eq!(Term::default().colorize("sky", Color::Blue), "sky");
+        assert!(!Term::default().color());
+    }
+
+    #[test]
+    fn existing_escape_codes_are_left_alone() {
+        let inner = paint("x", Color::Red);
+        assert_eq!(paint(&inner, Color::Blue), "\x1b[34m\x1b[31mx\x1b[0m\x1b[0m");
+    }
+
+    #[test]
+    fn primary_colors_convert() {
+        assert_eq!(Color::from(PrimaryColor::Blue), Color::Blue);
+        assert_eq!(Color::from(&PrimaryColors::Red("apple")), Color::Red);
+    }
+}
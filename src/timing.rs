@@ -0,0 +1,199 @@
+use crate::output::Output;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+//Where the time comes from. now only has to keep going up, what it is counted from doesn't
+// matter since only the difference between two calls is used.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+//The real clock, counting from whenever it was made.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+//A clock that moves forward by the same step every time it is looked at, so the durations come
+// out the same on every run. now takes &self, so the time is kept in a Cell.
+pub struct FakeClock {
+    time: Cell<Duration>,
+    step: Duration,
+}
+
+impl FakeClock {
+    pub fn new(step: Duration) -> Self {
+        FakeClock { time: Cell::new(Duration::ZERO), step }
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        let now = self.time.get();
+        self.time.set(now + self.step);
+        now
+    }
+}
+
+//Label and duration for every scope that finished, in the order they finished in. A scope timed
+// inside another one gets the outer labels in front of its own, like `traits/sorting`.
+pub struct Timings<C: Clock = SystemClock> {
+    clock: C,
+    entries: Vec<(String, Duration)>,
+    open: Vec<String>,
+}
+
+impl Timings {
+    pub fn new() -> Self {
+        Timings::with_clock(SystemClock::new())
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock> Timings<C> {
+    pub fn with_clock(clock: C) -> Self {
+        Timings { clock, entries: Vec::new(), open: Vec::new() }
+    }
+
+    //f is handed the collector back so it can time scopes of its own inside this one. While it
+    // runs, this label is on the open stack and every nested label gets qualified with it.
+    pub fn time<T>(&mut self, label: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.open.push(label.to_string());
+        let qualified = self.open.join("/");
+        let start = self.clock.now();
+        let value = f(self);
+        let elapsed = self.clock.now() - start;
+        self.open.pop();
+        self.entries.push((qualified, elapsed));
+        value
+    }
+
+    pub fn entries(&self) -> &[(String, Duration)] {
+        &self.entries
+    }
+
+    //Slowest first. Scopes that took the same time stay in the order they finished in.
+    pub fn summary(&self) -> String {
+        let mut sorted: Vec<&(String, Duration)> = self.entries.iter().collect();
+        sorted.sort_by_key(|(_, elapsed)| std::cmp::Reverse(*elapsed));
+        let width = sorted.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let lines: Vec<String> = sorted
+            .iter()
+            .map(|(label, elapsed)| format!("{label:<width$}  {:?}", elapsed))
+            .collect();
+        lines.join("\n")
+    }
+}
+
+//Times a single scope on its own against the real clock.
+pub fn timed<T>(label: &str, f: impl FnOnce() -> T) -> (T, Duration) {
+    let mut timings = Timings::new();
+    let value = timings.time(label, |_| f());
+    (value, timings.entries[0].1)
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let mut timings = Timings::with_clock(FakeClock::new(Duration::from_millis(1)));
+    let total = timings.time("shapes", |timings| {
+        let squares = timings.time("squares", |_| (1..=3).map(|side| side * side).sum::<isize>());
+        let triangles = timings.time("triangles", |timings| {
+            timings.time("halving", |_| (1..=3).map(|side| side * 4 / 2).sum::<isize>())
+        });
+        squares + triangles
+    });
+    timings.time("empty", |_| ());
+    out.line(format!("timed sum: {total}"));
+    for line in timings.summary().lines() {
+        out.line(line.to_string());
+    }
+
+    let (value, elapsed) = timed("real clock", || 6 * 7);
+    let quick = elapsed.as_secs() == 0;
+    out.line(format!("timed with the real clock: {value} (took less than a second? {quick})"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake() -> Timings<FakeClock> {
+        Timings::with_clock(FakeClock::new(Duration::from_millis(1)))
+    }
+
+    #[test]
+    fn fake_clock_steps_every_call() {
+        let clock = FakeClock::new(Duration::from_millis(5));
+        assert_eq!(clock.now(), Duration::ZERO);
+        assert_eq!(clock.now(), Duration::from_millis(5));
+        assert_eq!(clock.now(), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn nested_labels_are_qualified() {
+        let mut timings = fake();
+        timings.time("outer", |timings| {
+            timings.time("inner", |timings| timings.time("deepest", |_| ()));
+            timings.time("second", |_| ());
+        });
+        timings.time("after", |_| ());
+        let labels: Vec<&str> = timings.entries().iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["outer/inner/deepest", "outer/inner", "outer/second", "outer", "after"]
+        );
+    }
+
+    #[test]
+    fn nested_durations_include_the_inner_scopes() {
+        let mut timings = fake();
+        let value = timings.time("outer", |timings| timings.time("inner", |_| 7));
+        assert_eq!(value, 7);
+        let ms: Vec<u128> = timings.entries().iter().map(|(_, d)| d.as_millis()).collect();
+        //Every look at the fake clock adds 1ms, and outer looks twice around inner's two.
+        assert_eq!(ms, [1, 3]);
+    }
+
+    #[test]
+    fn summary_is_slowest_first_and_ties_keep_their_order() {
+        let mut timings = fake();
+        timings.time("a", |_| ());
+        timings.time("slow", |timings| timings.time("x", |_| ()));
+        timings.time("b", |_| ());
+        //a finished before slow/x did, so it stays in front of it.
+        assert_eq!(timings.summary(), "slow    3ms\na       1ms\nslow/x  1ms\nb       1ms");
+    }
+
+    #[test]
+    fn summary_of_nothing_is_empty() {
+        assert_eq!(fake().summary(), "");
+    }
+
+    #[test]
+    fn timed_returns_the_value() {
+        let (value, elapsed) = timed("sum", || (1..=10).sum::<i32>());
+        assert_eq!(value, 55);
+        assert!(elapsed < Duration::from_secs(1));
+    }
+}
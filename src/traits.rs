@@ -0,0 +1,2107 @@
+use crate::builder::ShapeBuilder;
+use crate::cached;
+use crate::collection::ShapeCollection;
+use crate::composite;
+use crate::diff::diff;
+use crate::generics::{Interval, Point, Rectangle, Stack};
+use crate::grid;
+use crate::json;
+use crate::leaderboard;
+use crate::observer;
+use crate::output::Output;
+use crate::parse;
+use crate::polygon;
+use crate::registry;
+use crate::render;
+use crate::report::SectionReport;
+use crate::rng;
+use crate::spatial::{self, Spatial};
+use crate::table;
+use crate::term::{Color, Term};
+use crate::triangle;
+use crate::units;
+use std::any::Any;
+use std::fmt::Display;
+use std::ops::{Add, Deref, Mul};
+
+//A trait is similar to an interface.
+
+//An example of a trait.
+pub trait Shape {
+    fn area(&self) -> isize;
+
+    //area overflows for big enough shapes, which panics in a debug build and silently wraps
+    // around in a release one. This is the version that gives back None instead. The default
+    // can only trust area, so shapes that multiply their sides together override it.
+    fn checked_area(&self) -> Option<isize> {
+        Some(self.area())
+    }
+
+    //Not every shape has a whole number area (a circle for example). Shapes that can have a
+    // fractional area override this, everything else just falls back on the integer area.
+    fn area_f64(&self) -> f64 {
+        self.area() as f64
+    }
+
+    //No default for this one. A made up 0.0 would let a new shape compile with a perimeter that
+    // is quietly wrong, so every shape has to say what its perimeter is.
+    fn perimeter(&self) -> f64;
+
+    //Traits can have a default behavior for a method.
+    fn default(&self, out: &mut dyn Output) {
+        out.line(String::from("default called"));
+    }
+
+    //Clone can't be a supertrait of Shape, clone returns Self and that would stop Shape from
+    // being used as a trait object. Every shape hands back a boxed copy of itself instead, which
+    // is what the Clone impl for Box<dyn Shape> below uses.
+    fn clone_box(&self) -> Box<dyn Shape>;
+
+    //A trait object only knows about Shape, so this is how to get from a &dyn Shape to its
+    // position in space. Shapes that don't implement Spatial stay with the default of None.
+    fn as_spatial(&self) -> Option<&dyn Spatial> {
+        None
+    }
+
+    //The last resort for getting the concrete type back out of a trait object. Any can be asked
+    // whether it is a particular type, see downcast_square.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl Clone for Box<dyn Shape> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+//Debug can't be derived for a trait object either, so this prints what every Shape can say about
+// itself. It is what lets a Box<dyn Shape> be printed with {:?}.
+impl std::fmt::Debug for dyn Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Shape {{ area: {} }}", self.area_f64())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangle {
+    pub base: isize,
+    pub height: isize,
+}
+
+//Squares are ordered by height, and with only the one field that is exactly what deriving does.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Square {
+    pub height: isize,
+}
+
+//Nothing stops building a shape with a negative or zero side directly, but going through the
+// constructors makes sure every dimension is positive.
+#[derive(Debug, PartialEq)]
+pub enum ShapeError {
+    NonPositiveBase(isize),
+    NonPositiveHeight(isize),
+    NonPositiveSide(f64),
+    NotATriangle(f64, f64, f64),
+    NotASquare(isize, isize),
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ShapeError::NonPositiveBase(base) => write!(f, "base must be positive, got {base}"),
+            ShapeError::NonPositiveHeight(height) => {
+                write!(f, "height must be positive, got {height}")
+            }
+            ShapeError::NonPositiveSide(side) => write!(f, "side must be positive, got {side}"),
+            ShapeError::NotATriangle(a, b, c) => {
+                write!(f, "sides {a}, {b} and {c} can't make a triangle")
+            }
+            ShapeError::NotASquare(height, width) => {
+                write!(f, "a {height} by {width} rectangle isn't a square")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+//An area, or a sum of areas, that is too big to fit in an isize.
+#[derive(Debug, PartialEq)]
+pub struct OverflowError;
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the area is too big to fit in an isize")
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+impl Triangle {
+    pub fn new(base: isize, height: isize) -> Result<Triangle, ShapeError> {
+        if base <= 0 {
+            return Err(ShapeError::NonPositiveBase(base));
+        }
+        if height <= 0 {
+            return Err(ShapeError::NonPositiveHeight(height));
+        }
+        Ok(Triangle { base, height })
+    }
+}
+
+impl Square {
+    pub fn new(height: isize) -> Result<Square, ShapeError> {
+        if height <= 0 {
+            return Err(ShapeError::NonPositiveHeight(height));
+        }
+        Ok(Square { height })
+    }
+}
+
+//Every square is a rectangle, so going that way is a From. Going back can fail, so it's a TryFrom
+// instead. The only check is that the sides match, a 0 by 0 rectangle becomes a 0 square the same
+// as building one directly would.
+impl From<Square> for Rectangle<isize> {
+    fn from(square: Square) -> Self {
+        Rectangle { height: square.height, width: square.height }
+    }
+}
+
+impl TryFrom<Rectangle<isize>> for Square {
+    type Error = ShapeError;
+
+    fn try_from(rectangle: Rectangle<isize>) -> Result<Self, Self::Error> {
+        if rectangle.height != rectangle.width {
+            return Err(ShapeError::NotASquare(rectangle.height, rectangle.width));
+        }
+        Ok(Square { height: rectangle.height })
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> isize {
+        (self.height * self.base)/2
+    }
+
+    fn checked_area(&self) -> Option<isize> {
+        self.height.checked_mul(self.base)?.checked_div(2)
+    }
+
+    //The triangles here are right triangles, so the third side is the hypotenuse.
+    fn perimeter(&self) -> f64 {
+        let base = self.base as f64;
+        let height = self.height as f64;
+        base + height + base.hypot(height)
+    }
+
+    fn default(&self, out: &mut dyn Output) {
+        out.line(String::from("Triangle default called."));
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_spatial(&self) -> Option<&dyn Spatial> {
+        Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> isize {
+        self.height * self.height
+    }
+
+    fn checked_area(&self) -> Option<isize> {
+        self.height.checked_mul(self.height)
+    }
+
+    fn perimeter(&self) -> f64 {
+        (self.height * 4) as f64
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_spatial(&self) -> Option<&dyn Spatial> {
+        Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl Shape for Circle {
+    //The integer area is rounded to the nearest whole number (halves round away from zero).
+    fn area(&self) -> isize {
+        self.area_f64().round() as isize
+    }
+
+    fn area_f64(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * std::f64::consts::PI * self.radius
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+//Traits can have associated constants as well as methods. They can't go on Shape itself, a
+// trait object has no way of knowing which type's constant to use, so Shape wouldn't be object
+// safe anymore. Keeping them in a trait of their own means they are only there for generic
+// code, where the concrete type is known at compile time.
+pub trait ShapeInfo {
+    const SIDES: usize;
+    const NAME: &'static str;
+}
+
+impl ShapeInfo for Triangle {
+    const SIDES: usize = 3;
+    const NAME: &'static str = "triangle";
+}
+
+impl ShapeInfo for Square {
+    const SIDES: usize = 4;
+    const NAME: &'static str = "square";
+}
+
+//A circle has no straight sides at all.
+impl ShapeInfo for Circle {
+    const SIDES: usize = 0;
+    const NAME: &'static str = "circle";
+}
+
+pub fn info_line<T: Shape + ShapeInfo>(s: &T) -> String {
+    format!("{} with {} sides, area {}", T::NAME, T::SIDES, s.area())
+}
+
+//Nothing of type T is needed to get at its constants, only the type itself.
+pub fn total_sides<T: ShapeInfo>(count: usize) -> usize {
+    T::SIDES * count
+}
+
+//We cannot do something where we implement an external trait on an external struct. For
+// example the below is now allowed.
+// impl Display for Vec<T>
+// However the below two lines ARE allowed.
+// impl Display for Triangle
+// impl Shape for Vec<T>
+// This rules assures that other people's code cannot break my code and vice versa. Otherwise
+// two crates could implement the same trait for the same type and the compiler wouldn't know
+// which to use.
+
+//Implementing Display (an external trait) for Triangle and Square (my own structs).
+impl Display for Triangle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Triangle(base={}, height={})", self.base, self.height)
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Square(height={})", self.height)
+    }
+}
+
+//The usual way around the rule above is a newtype. Wrapper is a local type, so Display can be
+// implemented on it even though all it holds is a Vec.
+pub struct Wrapper<T: Display>(pub Vec<T>);
+
+impl<T: Display> Display for Wrapper<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let items: Vec<String> = self.0.iter().map(|item| item.to_string()).collect();
+        write!(f, "[{}]", items.join(", "))
+    }
+}
+
+//Deref lets the wrapper be used like the Vec inside it, so things like len() and indexing
+// still work without re-implementing them.
+impl<T: Display> Deref for Wrapper<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+//The other half of the rule, actually done. Vec isn't mine but Shape is, so this is allowed. A
+// Vec of squares is treated as all of them laid side by side, so the areas add up.
+impl Shape for Vec<Square> {
+    fn area(&self) -> isize {
+        self.iter().map(|square| square.area()).sum()
+    }
+
+    fn checked_area(&self) -> Option<isize> {
+        self.iter().try_fold(0isize, |total, square| total.checked_add(square.checked_area()?))
+    }
+
+    //An empty Vec should have a perimeter of 0, and sum would give -0.0 for that.
+    fn perimeter(&self) -> f64 {
+        self.iter().fold(0.0, |total, square| total + square.perimeter())
+    }
+
+    fn default(&self, out: &mut dyn Output) {
+        out.line(format!("Vec of {} squares default called.", self.len()));
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+//The same thing as a newtype. Given the choice I would go with this one. Methods of my own can
+// only go on a type of my own, so push and largest can live here but could never be added
+// to Vec<Square> (short of yet another trait). It also means only a Squares is a Shape, rather
+// than every Vec<Square> anywhere in the crate quietly being one.
+#[derive(Clone, Default)]
+pub struct Squares(Vec<Square>);
+
+impl Squares {
+    pub fn new() -> Self {
+        Squares(Vec::new())
+    }
+
+    pub fn push(&mut self, square: Square) {
+        self.0.push(square);
+    }
+
+    //Square is Ord by height, so the largest square is the tallest one.
+    pub fn largest(&self) -> Option<&Square> {
+        self.0.iter().max()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Shape for Squares {
+    fn area(&self) -> isize {
+        self.0.area()
+    }
+
+    fn checked_area(&self) -> Option<isize> {
+        self.0.checked_area()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.0.perimeter()
+    }
+
+    fn default(&self, out: &mut dyn Output) {
+        out.line(format!("Squares of {} default called.", self.0.len()));
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+//A supertrait. Anything that wants OutlinePrint has to implement Display first, which is what
+// lets the default method use to_string on self.
+//Every line of the Display output is padded out to the longest one, and empty output is treated
+// as a single empty line so the frame is still drawn.
+pub trait OutlinePrint: Display {
+    fn outline(&self) -> String {
+        let text = self.to_string();
+        let lines: Vec<&str> = if text.is_empty() { vec![""] } else { text.lines().collect() };
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+
+        let mut framed = vec!["*".repeat(width + 4), format!("*{}*", " ".repeat(width + 2))];
+        for line in lines {
+            framed.push(format!("* {line:<width$} *"));
+        }
+        framed.push(format!("*{}*", " ".repeat(width + 2)));
+        framed.push("*".repeat(width + 4));
+        framed.join("\n")
+    }
+
+    fn outline_print(&self, out: &mut dyn Output) {
+        out.line(self.outline());
+    }
+}
+
+impl OutlinePrint for Square {}
+
+impl<T: Display, U: Display> OutlinePrint for Point<T, U> {}
+
+//Scaling returns Self, and a trait object can't know what Self is. Putting it in its own trait
+// keeps Shape usable as `dyn Shape`.
+pub trait Scalable {
+    fn scaled(&self, factor: isize) -> Self;
+}
+
+impl Scalable for Square {
+    fn scaled(&self, factor: isize) -> Self {
+        Square { height: self.height * factor }
+    }
+}
+
+impl Scalable for Triangle {
+    fn scaled(&self, factor: isize) -> Self {
+        Triangle { base: self.base * factor, height: self.height * factor }
+    }
+}
+
+//Operator overloading is also done through traits, `square * 3` is really `square.mul(3)`.
+impl Mul<isize> for Square {
+    type Output = Square;
+
+    fn mul(self, factor: isize) -> Square {
+        self.scaled(factor)
+    }
+}
+
+impl Mul<isize> for Triangle {
+    type Output = Triangle;
+
+    fn mul(self, factor: isize) -> Triangle {
+        self.scaled(factor)
+    }
+}
+
+//The same description built two ways. describe_shape gets a copy made for every T it is used
+// with (static dispatch), describe_dyn is a single function going through the vtable (dynamic
+// dispatch). Both give back exactly the same string for the same shape.
+//A whole number f64 prints without a decimal point, so the integer shapes look the same as
+// they would using area().
+//The ?Sized lets T be dyn Shape itself, so a &dyn Shape can be passed in as well.
+pub fn describe_shape<T: Shape + ?Sized>(shape: &T) -> String {
+    format!("Area is {}", shape.area_f64())
+}
+
+pub fn describe_dyn(shape: &dyn Shape) -> String {
+    format!("Area is {}", shape.area_f64())
+}
+
+//This would be how to use something like polymorphism with a trait.
+pub fn get_area(shape: &impl Shape, out: &mut dyn Output) {
+    out.line(describe_shape(shape));
+}
+
+//The above is syntactic sugar for this method itself.
+pub fn get_area_long<T: Shape>(shape: &T, out: &mut dyn Output) {
+    out.line(describe_shape(shape));
+}
+
+//Prints everything the trait knows about a shape.
+pub fn describe(shape: &impl Shape, out: &mut dyn Output) {
+    out.line(format!(
+        "Area is {} and perimeter is {}",
+        shape.area_f64(),
+        shape.perimeter()
+    ));
+}
+
+//It is possible to specify more than one trait at a time as required for the parameter.
+pub fn get_distance_and_area(shape: &(impl Shape + Display), out: &mut dyn Output) {
+    out.line(format!("{} has area {}", shape, shape.area_f64()));
+}
+
+//Once the bounds get long a `where` clause keeps the signature readable. It means exactly the
+// same thing as writing the bounds inside the angle brackets.
+pub fn compare_and_report<T, U>(t: &T, u: &U) -> String
+where
+    T: Shape + Display,
+    U: Shape + Display,
+{
+    let (t_area, u_area) = (t.area(), u.area());
+    if t_area > u_area {
+        format!("{t} is larger than {u} by {}", t_area - u_area)
+    } else if u_area > t_area {
+        format!("{u} is larger than {t} by {}", u_area - t_area)
+    } else {
+        format!("{t} and {u} have the same area")
+    }
+}
+
+//A where clause can also put a bound on a type that belongs to another type. Anything that can
+// be iterated over works, as long as what comes out of it is a Shape.
+pub fn sum_areas<I>(shapes: I) -> isize
+where
+    I: IntoIterator,
+    I::Item: Shape,
+{
+    shapes.into_iter().map(|shape| shape.area()).sum()
+}
+
+//Every Shape is Measurable through the blanket impl, with the area as its measure. The ?Sized
+// means a dyn Shape gets it as well.
+pub trait Measurable {
+    fn measure(&self) -> isize;
+}
+
+impl<T: Shape + ?Sized> Measurable for T {
+    fn measure(&self) -> isize {
+        self.area()
+    }
+}
+
+pub fn area_in_range(shape: &dyn Shape, range: &Interval<isize>) -> bool {
+    range.contains(&shape.area())
+}
+
+//T and U are separate generics, so a square can be compared to a triangle. Hands back the
+// larger of the two measures, which is a plain number so no lifetimes are needed.
+pub fn larger<T: Measurable, U: Measurable>(a: &T, b: &U) -> isize {
+    a.measure().max(b.measure())
+}
+
+//Largest first. The sort is stable, so items that measure the same keep their order.
+pub fn rank<T: Measurable>(items: &mut [T]) {
+    items.sort_by_key(|item| std::cmp::Reverse(item.measure()));
+}
+
+//The blanket implementation version of the same bound. Any type that is both a Shape and
+// Display gets describe for free, without writing an impl for each shape.
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: Shape + Display> Describe for T {
+    fn describe(&self) -> String {
+        format!("{} has area {}", self, self.area())
+    }
+}
+
+//Circle is a Shape but doesn't implement Display, so it never gets describe. The below does not
+// compile.
+// Circle{radius: 2.0}.describe();
+
+//A blanket impl with only a Display bound reaches much further than my own shapes. i32, String
+// and anything else in std that implements Display now has log_line too.
+pub trait Loggable {
+    fn log_line(&self) -> String;
+}
+
+impl<T: Display> Loggable for T {
+    fn log_line(&self) -> String {
+        format!("[log] {self}")
+    }
+}
+
+//Because Square implements Display the blanket impl already covers it, so writing my own impl
+// for it is rejected as a conflicting implementation.
+// impl Loggable for Square { ...
+//A type that is only Debug, like the generic Rectangle, gets nothing. The below does not compile.
+// Rectangle{height: 1, width: 2}.log_line();
+
+//Can also return a trait.
+pub fn return_area() -> impl Shape {
+    Square{height: 15}
+}
+
+//However, cannot return different types. The below code does not compile. Apparently there is
+// a way to make this work, but it won't be covered in the book for a while.
+// pub fn return_area_switch(switch: bool) -> impl Shape {
+//     if switch {
+//         Square{height: 15}
+//     } else {
+//         Triangle{height: 4, base: 1}
+//     }
+// }
+
+//The way to make it work is a trait object. The Box puts the shape on the heap so both
+// branches return the same type (a pointer), and the method to call is looked up at runtime.
+pub fn return_area_switch(switch: bool) -> Box<dyn Shape> {
+    if switch {
+        Box::new(Square{height: 15})
+    } else {
+        Box::new(Triangle{height: 4, base: 1})
+    }
+}
+
+//The other way around it is an enum with a variant for each type that could come back. There is
+// no Box and nothing on the heap, but the set of types is fixed to the two in the signature.
+#[derive(Debug)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    pub fn map_left<T, F: FnOnce(L) -> T>(self, f: F) -> Either<T, R> {
+        match self {
+            Either::Left(left) => Either::Left(f(left)),
+            Either::Right(right) => Either::Right(right),
+        }
+    }
+
+    pub fn map_right<T, F: FnOnce(R) -> T>(self, f: F) -> Either<L, T> {
+        match self {
+            Either::Left(left) => Either::Left(left),
+            Either::Right(right) => Either::Right(f(right)),
+        }
+    }
+}
+
+impl<L: Shape, R: Shape> Either<L, R> {
+    pub fn as_shape(&self) -> &dyn Shape {
+        match self {
+            Either::Left(left) => left,
+            Either::Right(right) => right,
+        }
+    }
+
+    pub fn area(&self) -> isize {
+        self.as_shape().area()
+    }
+}
+
+pub fn return_area_either(switch: bool) -> Either<Square, Triangle> {
+    if switch {
+        Either::Left(Square{height: 15})
+    } else {
+        Either::Right(Triangle{height: 4, base: 1})
+    }
+}
+
+//None unless the shape really is a Square. A clone made through clone_box is still a Square
+// underneath, so it downcasts the same as the original.
+pub fn downcast_square(shape: &dyn Shape) -> Option<&Square> {
+    shape.as_any().downcast_ref::<Square>()
+}
+
+//Trait objects also mean a single collection can hold different types of shapes.
+pub fn make_shapes(n: usize) -> Vec<Box<dyn Shape>> {
+    (0..n).map(|i| return_area_switch(i % 2 == 0)).collect()
+}
+
+//Iterating over trait objects works the same as any other slice, each element just happens
+// to be a different type behind the Box.
+//Both a single area and the running total can overflow, and either one stops the sum.
+pub fn total_area(shapes: &[Box<dyn Shape>]) -> Result<isize, OverflowError> {
+    shapes.iter().try_fold(0isize, |total, shape| {
+        shape.checked_area().and_then(|area| total.checked_add(area)).ok_or(OverflowError)
+    })
+}
+
+//There is no sensible average of nothing, so an empty slice gives None. Neither is there one
+// of shapes whose areas can't be added up.
+pub fn average_area(shapes: &[Box<dyn Shape>]) -> Option<f64> {
+    if shapes.is_empty() {
+        None
+    } else {
+        total_area(shapes).ok().map(|total| total as f64 / shapes.len() as f64)
+    }
+}
+
+//An extension trait. Slices come from the standard library so I can't add methods to them
+// directly, but I can add them through a trait of my own once it is in scope. Every reference
+// that comes out borrows from the slice, which elision works out from &self.
+//total_area here can't overflow. Once the sum (or a single area) gets too big it stays at
+// isize::MAX, so use the total_area function instead to find out whether that happened.
+pub trait SliceShapeExt {
+    fn total_area(&self) -> isize;
+    fn largest(&self) -> Option<&dyn Shape>;
+    fn partition_by_area(&self, threshold: isize) -> (Vec<&dyn Shape>, Vec<&dyn Shape>);
+}
+
+impl SliceShapeExt for [Box<dyn Shape>] {
+    fn total_area(&self) -> isize {
+        self.iter().fold(0isize, |total, shape| {
+            total.saturating_add(shape.checked_area().unwrap_or(isize::MAX))
+        })
+    }
+
+    //max_by_key gives back the last of several equal shapes, so going through the slice
+    // backwards means the first one wins instead.
+    fn largest(&self) -> Option<&dyn Shape> {
+        self.iter().rev().max_by_key(|shape| shape.area()).map(|shape| shape.as_ref())
+    }
+
+    //Shapes below the threshold go in the first Vec, and shapes at or above it in the second.
+    // Both keep the order of the slice.
+    fn partition_by_area(&self, threshold: isize) -> (Vec<&dyn Shape>, Vec<&dyn Shape>) {
+        self.iter().map(|shape| shape.as_ref()).partition(|shape| shape.area() < threshold)
+    }
+}
+
+//The example the book uses for most of the chapter. summarize has a default that relies on
+// summarize_author, which every type has to provide.
+pub trait Summary {
+    fn summarize_author(&self) -> String;
+
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.summarize_author())
+    }
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+    pub reply: bool,
+    pub retweet: bool,
+}
+
+//Tweet only fills in the required method and gets summarize for free.
+impl Summary for Tweet {
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub location: String,
+    pub author: String,
+    pub content: String,
+}
+
+//NewsArticle replaces the default summarize with its own.
+impl Summary for NewsArticle {
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    fn summarize(&self) -> String {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+}
+
+pub fn notify(item: &impl Summary, out: &mut dyn Output) {
+    out.line(format!("Breaking news! {}", item.summarize()));
+}
+
+//Trait objects and lifetimes together. Either argument could come back so they share 'a, the
+// same as largest_ref in the lifetimes module. On a tie the first one wins.
+pub fn largest_shape<'a>(a: &'a dyn Shape, b: &'a dyn Shape) -> &'a dyn Shape {
+    if b.area() > a.area() {
+        b
+    } else {
+        a
+    }
+}
+
+//The returned reference points into the slice, not at anything made inside the function.
+pub fn largest_in<'a>(shapes: &'a [Box<dyn Shape>]) -> Option<&'a dyn Shape> {
+    let mut iter = shapes.iter();
+    let mut largest: &'a dyn Shape = iter.next()?.as_ref();
+    for shape in iter {
+        largest = largest_shape(largest, shape.as_ref());
+    }
+    Some(largest)
+}
+
+//I can do something were I only implement the trait under certain conditions. I will use the
+// example directly from the book for this one.
+pub struct Pair<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Pair<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn swap(self) -> Pair<T> {
+        Pair { x: self.y, y: self.x }
+    }
+
+    pub fn into_tuple(self) -> (T, T) {
+        (self.x, self.y)
+    }
+
+    pub fn x_mut(&mut self) -> &mut T {
+        &mut self.x
+    }
+
+    pub fn y_mut(&mut self) -> &mut T {
+        &mut self.y
+    }
+
+    //Calling x_mut and then y_mut while the first one is still in use doesn't compile, because
+    // each call borrows all of self. Borrowing the two fields directly inside one method is fine
+    // though, the compiler can see that x and y never overlap.
+    pub fn both_mut(&mut self) -> (&mut T, &mut T) {
+        (&mut self.x, &mut self.y)
+    }
+}
+
+//Only available when T can be compared. Like cmp_display, x wins a tie.
+impl<T: PartialOrd> Pair<T> {
+    pub fn largest(&self) -> &T {
+        if self.x >= self.y {
+            &self.x
+        } else {
+            &self.y
+        }
+    }
+}
+
+//Any number of these conditional blocks can be stacked up on the same struct. A Pair<String>
+// can still be made with new, but it doesn't get sum or add because String isn't Copy. The below
+// does not compile.
+// Pair::new(String::from("a"), String::from("b")).sum();
+impl<T: Add<Output = T> + Copy> Pair<T> {
+    pub fn sum(&self) -> T {
+        self.x + self.y
+    }
+
+    pub fn add(&self, other: &Pair<T>) -> Pair<T> {
+        Pair::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: PartialOrd + Copy> Pair<T> {
+    //The smaller value always ends up in x.
+    pub fn sorted(&self) -> Pair<T> {
+        if self.y < self.x {
+            Pair::new(self.y, self.x)
+        } else {
+            Pair::new(self.x, self.y)
+        }
+    }
+}
+
+//This will only implement Display if T implements PartialOrd.
+impl<T: Display + PartialOrd> Pair<T> {
+    pub fn cmp_display(&self) -> String {
+        if self.x >= self.y {
+            format!("The largest member is x = {}", self.x)
+        } else {
+            format!("The largest member is y = {}", self.y)
+        }
+    }
+}
+
+//Every neighbouring pair, so [1, 2, 3] gives (1, 2) and (2, 3). The pairs hold references into
+// items and the '_ says the iterator can't outlive the slice it is borrowing from. Nothing is
+// cloned, which is why T doesn't need any bounds at all.
+pub fn pairs<T>(items: &[T]) -> impl Iterator<Item = Pair<&T>> + '_ {
+    items.windows(2).map(|window| Pair::new(&window[0], &window[1]))
+}
+
+//Splits items into pairs that don't overlap, so [1, 2, 3, 4] gives (1, 2) and (3, 4). An odd one
+// out at the end has nothing to pair with and is left out instead of being paired with a made
+// up value.
+pub fn pair_up<T: Clone>(items: &[T]) -> Vec<Pair<T>> {
+    items
+        .chunks_exact(2)
+        .map(|chunk| Pair::new(chunk[0].clone(), chunk[1].clone()))
+        .collect()
+}
+
+//A trait with an associated type. Item could have been a generic parameter instead, as in
+// Container<T>, but then one type could implement Container<i32> and Container<String> at the
+// same time and every caller would have to say which one they meant. Each container only ever
+// holds one kind of thing, so it gets to decide what Item is once, in its impl.
+pub trait Container {
+    type Item;
+
+    fn get(&self, i: usize) -> Option<&Self::Item>;
+    fn len(&self) -> usize;
+
+    fn first(&self) -> Option<&Self::Item> {
+        self.get(0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+//A Pair always holds exactly two things, x and then y.
+impl<T> Container for Pair<T> {
+    type Item = T;
+
+    fn get(&self, i: usize) -> Option<&T> {
+        match i {
+            0 => Some(&self.x),
+            1 => Some(&self.y),
+            _ => None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        2
+    }
+}
+
+//Written against Container alone, so it works the same for anything that implements it. The
+// where clause puts a bound on the associated type rather than on C itself.
+pub fn describe_container<C: Container>(c: &C) -> String
+where
+    C::Item: std::fmt::Debug,
+{
+    match c.first() {
+        Some(first) => format!("{} items, the first is {:?}", c.len(), first),
+        None => format!("{} items", c.len()),
+    }
+}
+
+//Two traits are allowed to have methods with the same name, and the struct can have one too.
+// square.fly() picks the method on the struct itself, the traits have to be named to get at
+// their versions.
+pub trait Pilot {
+    fn fly(&self) -> String;
+}
+
+pub trait Wizard {
+    fn fly(&self) -> String;
+}
+
+impl Square {
+    pub fn fly(&self) -> String {
+        String::from("*a square flapping its sides*")
+    }
+}
+
+impl Pilot for Square {
+    fn fly(&self) -> String {
+        String::from("This is your captain speaking.")
+    }
+}
+
+impl Wizard for Square {
+    fn fly(&self) -> String {
+        String::from("Up!")
+    }
+}
+
+//Associated functions have no self, so Animal::baby_name() on its own doesn't say which type to
+// use and won't compile. The fully qualified <Dog as Animal>::baby_name() does.
+pub trait Animal {
+    fn baby_name() -> String;
+}
+
+pub struct Dog;
+
+impl Dog {
+    pub fn baby_name() -> String {
+        String::from("Spot")
+    }
+}
+
+impl Animal for Dog {
+    fn baby_name() -> String {
+        String::from("puppy")
+    }
+}
+
+//The same as every other section, with colors off.
+pub fn run_demo(out: &mut dyn Output) -> SectionReport {
+    run_demo_with(out, Term::plain())
+}
+
+//This is the only section that colors anything in its own output, so it is the only one that
+// needs to be told whether colors are on.
+pub fn run_demo_with(out: &mut dyn Output, term: Term) -> SectionReport {
+    let mut report = SectionReport::new("traits");
+
+    report.demo("shape areas");
+    let my_triangle = Triangle::new(5, 10).expect("5 and 10 are both positive");
+    let my_square = Square::new(10).expect("10 is positive");
+    let my_circle = Circle{radius: 2.0};
+
+    out.line(my_triangle.log_line());
+    out.line(my_square.log_line());
+    let area = |shape: &dyn Shape| term.colorize(&shape.area().to_string(), Color::Yellow);
+    out.line(format!("triangle area: {}", area(&my_triangle)));
+    report.record("triangle area", my_triangle.area());
+    my_triangle.default(out);
+    out.line(format!("square area: {}", area(&my_square)));
+    report.record("square area", my_square.area());
+    my_square.default(out);
+    out.line(format!(
+        "circle area: {} (rounded {})",
+        my_circle.area_f64(),
+        area(&my_circle)
+    ));
+    my_circle.default(out);
+
+    report.demo("wrapper");
+    let wrapper = Wrapper(vec![String::from("hello"), String::from("world")]);
+    out.line(format!("w = {wrapper} has {} items, first is {}", wrapper.len(), wrapper[0]));
+    out.line(format!("numbers = {}", Wrapper(vec![1, 2, 3])));
+
+    report.demo("outline print");
+    my_square.outline_print(out);
+    Point { x: 1, y: 3.5 }.outline_print(out);
+
+    report.demo("scaling");
+    //Scaling the sides by 3 makes the area 9 times bigger.
+    let big_square = my_square.scaled(3);
+    out.line(format!(
+        "{} scaled by 3 is {} with area {}",
+        my_square,
+        big_square,
+        big_square.area()
+    ));
+    let big_triangle = Triangle { base: 5, height: 10 } * 3;
+    out.line(format!("{} has area {}", big_triangle, big_triangle.area()));
+
+    report.demo("constructors");
+    match Square::new(-10) {
+        Ok(square) => out.line(format!("made a square with area {}", square.area())),
+        Err(e) => out.line(format!("could not make a square: {e}")),
+    }
+
+    report.demo("conversions");
+    let rectangle = Rectangle::from(Square { height: 6 });
+    out.line(format!("square as a rectangle: {:?}", rectangle));
+    match Square::try_from(rectangle) {
+        Ok(square) => out.line(format!("and back again: {square}")),
+        Err(e) => out.line(format!("could not convert: {e}")),
+    }
+    let not_square: Result<Square, ShapeError> = Rectangle { height: 4, width: 9 }.try_into();
+    if let Err(e) = not_square {
+        out.line(format!("could not convert: {e}"));
+    }
+
+    report.demo("loggable");
+    out.line(42.log_line());
+    out.line(String::from("a plain string").log_line());
+
+    report.demo("get_area");
+    get_area(&my_triangle, out);
+    get_area(&my_square, out);
+    get_area(&my_circle, out);
+
+    report.demo("get_area_long");
+    get_area_long(&my_triangle, out);
+    get_area_long(&my_square, out);
+    get_area_long(&my_circle, out);
+
+    report.demo("vec as a shape");
+    let vec_of_squares = Vec::from([Square { height: 2 }, Square { height: 3 }]);
+    get_area(&vec_of_squares, out);
+    vec_of_squares.default(out);
+    let mut squares = Squares::new();
+    squares.push(Square { height: 4 });
+    squares.push(Square { height: 6 });
+    squares.push(Square { height: 5 });
+    get_area(&squares, out);
+    squares.default(out);
+    let tallest = squares.largest().map(|s| s.height);
+    out.line(format!("largest of {} squares: {:?}", squares.len(), tallest));
+
+    report.demo("describe");
+    describe(&my_triangle, out);
+    describe(&my_square, out);
+    describe(&my_circle, out);
+    describe(&Triangle{base: 3, height: 4}, out);
+
+    report.demo("multiple bounds");
+    get_distance_and_area(&my_triangle, out);
+    get_distance_and_area(&my_square, out);
+    out.line(my_triangle.describe());
+    out.line(my_square.describe());
+
+    report.demo("shape info");
+    out.line(info_line(&my_triangle));
+    out.line(info_line(&my_square));
+    out.line(info_line(&my_circle));
+    out.line(info_line(&polygon::RegularPolygon::<6>::new(2.0)));
+    if let Ok(sided) = triangle::SidedTriangle::new(3.0, 4.0, 5.0) {
+        out.line(info_line(&sided));
+    }
+    out.line(format!(
+        "a square and a triangle have {} sides, 3 squares have {}",
+        Square::SIDES + Triangle::SIDES,
+        total_sides::<Square>(3)
+    ));
+
+    report.demo("where clauses");
+    out.line(compare_and_report(&my_square, &my_triangle));
+    out.line(compare_and_report(&Triangle { base: 4, height: 8 }, &Square { height: 4 }));
+    let squares = Vec::from([Square { height: 1 }, Square { height: 2 }, Square { height: 3 }]);
+    let triangles = [Triangle { base: 2, height: 2 }, Triangle { base: 4, height: 4 }];
+    out.line(format!(
+        "sum of squares: {} sum of triangles: {} sum of an iterator: {}",
+        sum_areas(squares),
+        sum_areas(triangles),
+        sum_areas((1..=4).map(|height| Square { height }))
+    ));
+
+    report.demo("diff");
+    out.line(format!("square to triangle: {}", diff(&my_square, &my_triangle)));
+    out.line(format!("square to a bigger square: {}", diff(&my_square, &Square { height: 12 })));
+    out.line(format!("square to itself: {}", diff(&my_square, &my_square)));
+
+    report.demo("measurable");
+    out.line(format!(
+        "larger of {} and {}: {}",
+        my_square,
+        my_triangle,
+        larger(&my_square, &my_triangle)
+    ));
+    let medium = Interval::new(20, 50).expect("20 is below 50");
+    out.line(format!(
+        "area in [20, 50]? square: {} triangle: {}",
+        area_in_range(&my_square, &medium),
+        area_in_range(&my_triangle, &medium)
+    ));
+    let mut ranked = [Square { height: 2 }, Square { height: 7 }, Square { height: 4 }];
+    rank(&mut ranked);
+    let ranked: Vec<isize> = ranked.iter().map(|square| square.height).collect();
+    out.line(format!("ranked heights: {:?}", ranked));
+
+    report.demo("return a trait");
+    return_area().default(out);
+
+    report.demo("trait objects");
+    let switched_square = return_area_switch(true);
+    let switched_triangle = return_area_switch(false);
+    out.line(format!("switched square area: {}", switched_square.area()));
+    switched_square.default(out);
+    out.line(format!("switched triangle area: {}", switched_triangle.area()));
+    switched_triangle.default(out);
+    out.line(describe_dyn(switched_triangle.as_ref()));
+
+    for shape in make_shapes(4) {
+        out.line(format!("made shape area: {}", shape.area()));
+    }
+
+    report.demo("downcasting");
+    let boxed: Box<dyn Shape> = Box::new(Square{height: 6});
+    let shapes: [Box<dyn Shape>; 3] =
+        [boxed.clone(), Box::new(Triangle{base: 2, height: 6}), boxed];
+    for shape in &shapes {
+        let detail = match downcast_square(shape.as_ref()) {
+            Some(square) => format!("a square with side {}", square.height),
+            None => String::from("something else"),
+        };
+        out.line(format!("area {} from {detail}", shape.area()));
+    }
+
+    report.demo("either");
+    let either_square = return_area_either(true);
+    let either_triangle = return_area_either(false);
+    out.line(format!("either areas: {} and {}", either_square.area(), either_triangle.area()));
+    out.line(describe_shape(either_triangle.as_shape()));
+    let doubled = either_square.map_left(|square| square * 2);
+    out.line(format!("mapped left: {}", doubled.as_shape().area()));
+    let relabeled = either_triangle.map_right(|triangle| triangle.to_string());
+    out.line(format!("mapped right: {:?}", relabeled.map_left(|square| square.height)));
+
+    report.demo("total and average area");
+    let mixed_shapes: Vec<Box<dyn Shape>> = [
+        ShapeBuilder::triangle().base(5).height(10).build(),
+        ShapeBuilder::square().height(10).build(),
+        ShapeBuilder::triangle().base(3).height(4).build(),
+        ShapeBuilder::square().height(2).build(),
+    ]
+    .into_iter()
+    .collect::<Result<_, _>>()
+    .expect("every builder has all of its fields filled in");
+    match total_area(&mixed_shapes) {
+        Ok(total) => {
+            out.line(format!("total area: {total}"));
+            report.record("total area", total);
+        }
+        Err(e) => out.line(format!("total area: {e}")),
+    }
+    out.line(format!("average area: {:?}", average_area(&mixed_shapes)));
+    out.line(format!("average area of nothing: {:?}", average_area(&[])));
+
+    report.demo("builders");
+    let half_built = ShapeBuilder::triangle().base(6);
+    for result in [half_built.clone().height(2).build(), half_built.build()] {
+        match result {
+            Ok(shape) => out.line(format!("built a shape with area {}", shape.area())),
+            Err(e) => out.line(format!("could not build a shape: {e}")),
+        }
+    }
+
+    report.demo("area tables");
+    table::run_demo(out);
+
+    report.demo("checked area");
+    let huge = Square { height: isize::MAX };
+    match total_area(&[huge.clone_box()]) {
+        Ok(total) => out.line(format!("side isize::MAX: area {total}")),
+        Err(e) => out.line(format!("side isize::MAX: {e}")),
+    }
+    let stuck = Vec::from([huge.clone_box(), huge.clone_box()]).total_area() == isize::MAX;
+    out.line(format!("through the slice it stops at isize::MAX: {stuck}"));
+
+    report.demo("slice extension");
+    out.line(format!("total area through the slice: {}", mixed_shapes.total_area()));
+    out.line(format!("largest area: {:?}", mixed_shapes.largest().map(|shape| shape.area())));
+    let no_shapes: Vec<Box<dyn Shape>> = Vec::new();
+    out.line(format!(
+        "empty slice total: {} largest: {:?}",
+        no_shapes.total_area(),
+        no_shapes.largest().map(|shape| shape.area())
+    ));
+    for threshold in [0, 6, 25, 1000] {
+        let (below, rest) = mixed_shapes.partition_by_area(threshold);
+        let areas = |shapes: &[&dyn Shape]| -> Vec<isize> {
+            shapes.iter().map(|shape| shape.area()).collect()
+        };
+        out.line(format!(
+            "below {threshold}: {:?} at or above: {:?}",
+            areas(&below),
+            areas(&rest)
+        ));
+    }
+
+    report.demo("largest shape");
+    let bigger = largest_shape(&my_triangle, &my_square);
+    out.line(format!("larger of triangle and square has area {}", bigger.area()));
+    if let Some(shape) = largest_in(&mixed_shapes) {
+        out.line(format!("largest of the mixed shapes has area {}", shape.area()));
+    }
+
+    report.demo("shape collection");
+    let mut collection = ShapeCollection::new();
+    collection.push(Box::new(Square{height: 3}));
+    collection.push(Box::new(Triangle{base: 5, height: 10}));
+    collection.push(Box::new(Square{height: 10}));
+    collection.push(Box::new(Triangle{base: 6, height: 3}));
+    collection.sort_by_area();
+    let sorted_areas: Vec<isize> = collection.iter().map(|shape| shape.area()).collect();
+    out.line(format!("sorted areas: {:?}", sorted_areas));
+    if let Some(shape) = collection.largest() {
+        out.line(format!("largest area in collection: {}", shape.area()));
+    }
+    //The copy gets its own boxes, so adding to the original afterwards leaves it alone.
+    let copy = collection.clone();
+    collection.push(Box::new(Square{height: 1}));
+    out.line(format!("original has {} shapes, the copy has {}", collection.len(), copy.len()));
+
+    report.demo("cached area");
+    cached::run_demo(out);
+
+    report.demo("composite shapes");
+    composite::run_demo(out);
+
+    report.demo("units");
+    units::run_demo(out);
+
+    report.demo("observers");
+    observer::run_demo(out);
+
+    report.demo("parsing shapes");
+    parse::run_demo(out);
+    report.demo("json");
+    json::run_demo(out);
+    report.demo("rendering");
+    render::run_demo(out);
+    report.demo("registry");
+    registry::run_demo(out);
+
+    report.demo("leaderboard");
+    leaderboard::run_demo(out);
+
+    report.demo("random shapes");
+    rng::run_demo(out);
+    report.demo("sided triangles");
+    triangle::run_demo(out);
+    report.demo("regular polygons");
+    polygon::run_demo(out);
+    report.demo("spatial");
+    spatial::run_demo(out);
+    report.demo("grid");
+    grid::run_demo(out);
+
+    report.demo("fully qualified syntax");
+    out.line(my_square.fly());
+    out.line(Pilot::fly(&my_square));
+    out.line(<Square as Wizard>::fly(&my_square));
+    out.line(format!("A baby dog is called a {}", Dog::baby_name()));
+    out.line(format!("A baby dog is called a {}", <Dog as Animal>::baby_name()));
+
+    report.demo("summary");
+    let tweet = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    };
+    let article = NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from(
+            "The Pittsburgh Penguins once again are the best hockey team in the NHL.",
+        ),
+    };
+    notify(&tweet, out);
+    notify(&article, out);
+
+    report.demo("pair");
+    let int_pair = Pair::new(3, 7);
+    out.line(int_pair.cmp_display());
+    out.line(format!("largest: {}", int_pair.largest()));
+    let string_pair = Pair::new(String::from("apple"), String::from("banana")).swap();
+    out.line(string_pair.cmp_display());
+    out.line(format!("into_tuple: {:?}", string_pair.into_tuple()));
+    out.line(Pair::new(5, 5).cmp_display());
+
+    report.demo("sorting squares");
+    let mut squares = Vec::from([Square { height: 4 }, Square { height: 1 }, Square { height: 3 }]);
+    squares.sort();
+    let heights: Vec<String> = squares.iter().map(|square| square.to_string()).collect();
+    out.line(format!("sorted: {}", heights.join(" ")));
+    out.line(format!("largest: {:?}", squares.iter().max().map(|square| square.height)));
+
+    report.demo("pairs from slices");
+    let numbers = [1, 5, 2, 8, 3];
+    let largest: Vec<String> = pairs(&numbers).map(|pair| pair.largest().to_string()).collect();
+    out.line(format!("largest of each neighbouring pair: {}", largest.join(" ")));
+    let paired: Vec<(i32, i32)> = pair_up(&numbers).into_iter().map(Pair::into_tuple).collect();
+    out.line(format!("paired up: {:?}", paired));
+
+    report.demo("containers");
+    let words = Pair::new("left", "right");
+    let mut stack = Stack::new();
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+    let mut shapes = ShapeCollection::new();
+    shapes.push(Box::new(Square { height: 3 }));
+    shapes.push(Box::new(Triangle { base: 2, height: 4 }));
+    out.line(describe_container(&words));
+    out.line(describe_container(&stack));
+    out.line(describe_container(&shapes));
+    out.line(describe_container(&Stack::<i32>::new()));
+    out.line(describe_container(&ShapeCollection::new()));
+    out.line(format!(
+        "get(1): {:?} {:?} {:?}",
+        Container::get(&words, 1),
+        Container::get(&stack, 1),
+        Container::get(&shapes, 1)
+    ));
+    out.line(format!(
+        "get(5): {:?} {:?} {:?}",
+        Container::get(&words, 5),
+        Container::get(&stack, 5),
+        Container::get(&shapes, 5)
+    ));
+
+    report.demo("split borrows");
+    let mut words = Pair::new(String::from("left"), String::from("right"));
+    let (x, y) = words.both_mut();
+    std::mem::swap(x, y);
+    x.push_str(" side");
+    y.make_ascii_uppercase();
+    words.x_mut().push('!');
+    *words.y_mut() += "?";
+    out.line(format!("words: ({}, {})", words.x, words.y));
+
+    report.demo("pair arithmetic");
+    let added = int_pair.add(&Pair::new(10, -10));
+    out.line(format!("sum: {} added: ({}, {})", int_pair.sum(), added.x, added.y));
+    let float_pair = Pair::new(2.5, 1.25).sorted();
+    out.line(format!("sorted: ({}, {}) sum: {}", float_pair.x, float_pair.y, float_pair.sum()));
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cached::CachedShape;
+    use crate::composite::CompositeShape;
+    use crate::polygon::RegularPolygon;
+    use crate::triangle::SidedTriangle;
+    use std::f64::consts::PI;
+
+    fn boxed_squares(side: isize, count: usize) -> Vec<Box<dyn Shape>> {
+        (0..count).map(|_| Box::new(Square { height: side }) as Box<dyn Shape>).collect()
+    }
+
+    #[test]
+    fn checked_area_fits_just_under_the_boundary() {
+        let max_side = isize::MAX.isqrt();
+        assert_eq!(Square { height: max_side }.checked_area(), Some(max_side * max_side));
+        assert_eq!(Square { height: max_side + 1 }.checked_area(), None);
+    }
+
+    #[test]
+    fn checked_area_of_max_sides_is_none() {
+        assert_eq!(Square { height: isize::MAX }.checked_area(), None);
+        assert_eq!(Triangle { base: isize::MAX, height: isize::MAX }.checked_area(), None);
+        assert_eq!(Triangle { base: isize::MAX, height: 1 }.checked_area(), Some(isize::MAX / 2));
+    }
+
+    #[test]
+    fn total_area_reports_overflow() {
+        let max_side = isize::MAX.isqrt();
+        assert_eq!(total_area(&boxed_squares(isize::MAX, 1)), Err(OverflowError));
+        assert_eq!(total_area(&boxed_squares(max_side, 1)), Ok(max_side * max_side));
+        assert_eq!(total_area(&boxed_squares(max_side, 2)), Err(OverflowError));
+    }
+
+    #[test]
+    fn slice_total_area_stops_at_the_max() {
+        let max_side = isize::MAX.isqrt();
+        assert_eq!(boxed_squares(max_side, 1).total_area(), max_side * max_side);
+        assert_eq!(boxed_squares(max_side, 3).total_area(), isize::MAX);
+        assert_eq!(boxed_squares(isize::MAX, 1).total_area(), isize::MAX);
+        assert_eq!(boxed_squares(10, 1000).total_area(), 100_000);
+        assert_eq!(boxed_squares(10, 0).total_area(), 0);
+    }
+
+    #[test]
+    fn total_area_of_many_small_shapes() {
+        assert_eq!(total_area(&[]), Ok(0));
+        assert_eq!(total_area(&boxed_squares(10, 1000)), Ok(100_000));
+    }
+
+    #[test]
+    fn average_area_is_none_on_overflow() {
+        assert_eq!(average_area(&boxed_squares(isize::MAX, 2)), None);
+        assert_eq!(average_area(&boxed_squares(2, 4)), Some(4.0));
+    }
+
+    #[test]
+    fn empty_vec_of_squares_has_no_area() {
+        let no_squares: Vec<Square> = Vec::new();
+        assert_eq!(no_squares.area(), 0);
+        assert_eq!(no_squares.checked_area(), Some(0));
+        assert_eq!(Squares::new().area(), 0);
+    }
+
+    #[test]
+    fn large_vec_of_squares_adds_up() {
+        let many_squares: Vec<Square> = (0..10_000).map(|_| Square { height: 10 }).collect();
+        assert_eq!(many_squares.area(), 1_000_000);
+        let mut out: Vec<String> = Vec::new();
+        get_area(&many_squares, &mut out);
+        assert_eq!(out, ["Area is 1000000"]);
+    }
+
+    #[test]
+    fn vec_of_squares_overflow_is_caught() {
+        let side = isize::MAX.isqrt();
+        let squares = Vec::from([Square { height: side }, Square { height: side }]);
+        assert_eq!(squares.checked_area(), None);
+    }
+
+    #[test]
+    fn largest_of_squares() {
+        let mut squares = Squares::new();
+        assert!(squares.largest().is_none());
+        assert!(squares.is_empty());
+        for height in [4, 6, 5] {
+            squares.push(Square { height });
+        }
+        assert_eq!(squares.largest(), Some(&Square { height: 6 }));
+        assert_eq!(squares.len(), 3);
+        assert_eq!(squares.area(), 77);
+    }
+
+    #[test]
+    fn areas_are_colored_only_when_asked() {
+        let mut colored: Vec<String> = Vec::new();
+        run_demo_with(&mut colored, Term::new(true));
+        assert!(colored.iter().any(|line| line.contains("\x1b[33m100\x1b[0m")));
+
+        let mut plain: Vec<String> = Vec::new();
+        run_demo(&mut plain);
+        assert!(plain.iter().all(|line| !line.contains('\x1b')));
+    }
+
+    fn described(shape: &impl Shape) -> String {
+        let mut out: Vec<String> = Vec::new();
+        describe(shape, &mut out);
+        assert_eq!(out.len(), 1);
+        out.remove(0)
+    }
+
+    #[test]
+    fn describe_triangle() {
+        let triangle = Triangle { base: 3, height: 4 };
+        assert_eq!(described(&triangle), "Area is 6 and perimeter is 12");
+    }
+
+    #[test]
+    fn describe_square() {
+        assert_eq!(described(&Square { height: 5 }), "Area is 25 and perimeter is 20");
+    }
+
+    #[test]
+    fn describe_circle() {
+        let circle = Circle { radius: 1.0 };
+        let expected = format!("Area is {} and perimeter is {}", PI, 2.0 * PI);
+        assert_eq!(described(&circle), expected);
+    }
+
+    #[test]
+    fn describe_vec_and_squares_add_up_their_squares() {
+        let squares = Vec::from([Square { height: 1 }, Square { height: 2 }]);
+        assert_eq!(described(&squares), "Area is 5 and perimeter is 12");
+        let mut newtype = Squares::new();
+        squares.into_iter().for_each(|square| newtype.push(square));
+        assert_eq!(described(&newtype), "Area is 5 and perimeter is 12");
+        assert_eq!(described(&Squares::new()), "Area is 0 and perimeter is 0");
+    }
+
+    #[test]
+    fn describe_composite_adds_up_its_parts() {
+        let mut composite = CompositeShape::new();
+        composite.push(Box::new(Square { height: 2 }));
+        composite.push(Box::new(Triangle { base: 3, height: 4 }));
+        assert_eq!(described(&composite), "Area is 10 and perimeter is 20");
+        assert_eq!(described(&CompositeShape::new()), "Area is 0 and perimeter is 0");
+    }
+
+    #[test]
+    fn describe_cached_is_the_same_as_the_inner_shape() {
+        let cached = CachedShape::new(Triangle { base: 3, height: 4 });
+        assert_eq!(described(&cached), described(&Triangle { base: 3, height: 4 }));
+    }
+
+    #[test]
+    fn describe_polygon() {
+        let hexagon = RegularPolygon::<6>::new(2.0);
+        let expected = format!("Area is {} and perimeter is 12", hexagon.area_f64());
+        assert_eq!(described(&hexagon), expected);
+        assert!((hexagon.area_f64() - 6.0 * 3f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn describe_sided_triangle() {
+        let triangle = SidedTriangle::new(3.0, 4.0, 5.0).unwrap();
+        assert_eq!(described(&triangle), "Area is 6 and perimeter is 12");
+    }
+
+    #[test]
+    fn average_of_nothing_is_none() {
+        assert_eq!(average_area(&[]), None);
+        assert_eq!(total_area(&[]), Ok(0));
+    }
+
+    #[test]
+    fn average_of_one_shape_is_its_area() {
+        let one: Vec<Box<dyn Shape>> = vec![Box::new(Triangle { base: 5, height: 10 })];
+        assert_eq!(average_area(&one), Some(25.0));
+    }
+
+    #[test]
+    fn average_of_mixed_shapes_can_be_fractional() {
+        let mixed: Vec<Box<dyn Shape>> = vec![
+            Box::new(Square { height: 3 }),
+            Box::new(Triangle { base: 2, height: 4 }),
+        ];
+        assert_eq!(total_area(&mixed), Ok(13));
+        assert_eq!(average_area(&mixed), Some(6.5));
+    }
+
+    #[test]
+    fn circle_area_is_pi_r_squared() {
+        let circle = Circle { radius: 2.0 };
+        assert!((circle.area_f64() - 12.566).abs() < 1e-3, "{}", circle.area_f64());
+        assert_eq!(circle.area_f64(), 4.0 * PI);
+        let mut out: Vec<String> = Vec::new();
+        get_area(&circle, &mut out);
+        get_area_long(&circle, &mut out);
+        assert_eq!(out, ["Area is 12.566370614359172", "Area is 12.566370614359172"]);
+    }
+
+    #[test]
+    fn circle_area_rounds_to_the_nearest_whole_number() {
+        let areas: Vec<isize> =
+            [0.0, 0.5, 1.0, 2.0, 10.0].iter().map(|&radius| Circle { radius }.area()).collect();
+        assert_eq!(areas, [0, 1, 3, 13, 314]);
+        //pi * r^2 lands on exactly 2.5 for this radius, which rounds up to 3 rather than to 2.
+        let half = Circle { radius: (2.5 / PI).sqrt() };
+        assert!((half.area_f64() - 2.5).abs() < 1e-12);
+        assert_eq!(half.area(), 3);
+    }
+
+    #[test]
+    fn integer_shapes_have_whole_float_areas() {
+        assert_eq!(Square { height: 3 }.area_f64(), 9.0);
+        assert_eq!(Triangle { base: 3, height: 3 }.area_f64(), 4.0);
+    }
+
+    #[test]
+    fn switch_picks_the_shape_to_box() {
+        let square = return_area_switch(true);
+        assert_eq!(square.as_any().downcast_ref::<Square>(), Some(&Square { height: 15 }));
+        assert_eq!(square.area(), Square { height: 15 }.area());
+        let triangle = return_area_switch(false);
+        let expected = Triangle { base: 1, height: 4 };
+        assert_eq!(triangle.as_any().downcast_ref::<Triangle>(), Some(&expected));
+        assert_eq!(triangle.area(), expected.area());
+    }
+
+    #[test]
+    fn make_shapes_alternates_starting_with_a_square() {
+        let shapes = make_shapes(5);
+        let squares: Vec<bool> = shapes.iter().map(|shape| shape.as_any().is::<Square>()).collect();
+        assert_eq!(squares, [true, false, true, false, true]);
+        let areas: Vec<isize> = shapes.iter().map(|shape| shape.area()).collect();
+        assert_eq!(areas, [225, 2, 225, 2, 225]);
+        assert!(make_shapes(0).is_empty());
+    }
+
+    #[test]
+    fn display_formats() {
+        assert_eq!(Triangle { base: 5, height: 10 }.to_string(), "Triangle(base=5, height=10)");
+        assert_eq!(Square { height: -3 }.to_string(), "Square(height=-3)");
+        let mut out: Vec<String> = Vec::new();
+        get_distance_and_area(&Square { height: 4 }, &mut out);
+        assert_eq!(out, ["Square(height=4) has area 16"]);
+    }
+
+    #[test]
+    fn pair_largest_of_ints_and_strings() {
+        assert_eq!(*Pair::new(3, 9).largest(), 9);
+        assert_eq!(*Pair::new(9, 3).largest(), 9);
+        let words = Pair::new(String::from("apple"), String::from("pear"));
+        assert_eq!(words.largest(), "pear");
+        assert_eq!(words.cmp_display(), "The largest member is y = pear");
+    }
+
+    #[test]
+    fn pair_tie_goes_to_x() {
+        let tie = Pair::new(String::from("same"), String::from("same"));
+        assert!(std::ptr::eq(tie.largest(), &tie.x));
+        assert_eq!(Pair::new(4, 4).cmp_display(), "The largest member is x = 4");
+    }
+
+    #[test]
+    fn pair_swap_and_into_tuple() {
+        assert_eq!(Pair::new(1, 2).swap().into_tuple(), (2, 1));
+        let words = Pair::new(String::from("a"), String::from("b"));
+        assert_eq!(words.into_tuple(), (String::from("a"), String::from("b")));
+    }
+
+    #[test]
+    fn constructors_reject_zero_and_negative() {
+        assert_eq!(Square::new(0), Err(ShapeError::NonPositiveHeight(0)));
+        assert_eq!(Square::new(-10), Err(ShapeError::NonPositiveHeight(-10)));
+        assert_eq!(Triangle::new(0, 5), Err(ShapeError::NonPositiveBase(0)));
+        assert_eq!(Triangle::new(5, 0), Err(ShapeError::NonPositiveHeight(0)));
+        assert_eq!(Triangle::new(0, 0), Err(ShapeError::NonPositiveBase(0)));
+        assert_eq!(Square::new(0).unwrap_err().to_string(), "height must be positive, got 0");
+    }
+
+    #[test]
+    fn constructors_accept_one_and_large_values() {
+        assert_eq!(Square::new(1), Ok(Square { height: 1 }));
+        assert_eq!(Square::new(isize::MAX), Ok(Square { height: isize::MAX }));
+        let triangle = Triangle::new(isize::MAX, 1).unwrap();
+        assert_eq!(triangle.checked_area(), Some(isize::MAX / 2));
+    }
+
+    fn tweet() -> Tweet {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course"),
+            reply: false,
+            retweet: false,
+        }
+    }
+
+    #[test]
+    fn tweet_uses_the_default_summary() {
+        assert_eq!(tweet().summarize_author(), "@horse_ebooks");
+        assert_eq!(tweet().summarize(), "(Read more from @horse_ebooks...)");
+    }
+
+    #[test]
+    fn news_article_overrides_the_summary() {
+        let article = NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from("The Pittsburgh Penguins once again are the best team."),
+        };
+        assert_eq!(
+            article.summarize(),
+            "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"
+        );
+        assert_eq!(article.summarize_author(), "Iceburgh");
+        let mut out: Vec<String> = Vec::new();
+        notify(&tweet(), &mut out);
+        assert_eq!(out, ["Breaking news! (Read more from @horse_ebooks...)"]);
+    }
+
+    #[test]
+    fn scaling_by_zero_and_one() {
+        let square = Square { height: 10 };
+        assert_eq!(square.scaled(0), Square { height: 0 });
+        assert_eq!(square.scaled(1), square);
+        let triangle = Triangle { base: 4, height: 6 };
+        assert_eq!(triangle.scaled(0).area(), 0);
+        assert_eq!(triangle.scaled(1), triangle);
+    }
+
+    #[test]
+    fn scaling_leaves_the_original_alone() {
+        let square = Square { height: 10 };
+        let tripled = square.scaled(3);
+        assert_eq!((square.height, tripled.height), (10, 30));
+        assert_eq!(tripled.area(), square.area() * 9);
+        let triangle = Triangle { base: 4, height: 6 };
+        let doubled = triangle.clone() * 2;
+        assert_eq!(doubled, Triangle { base: 8, height: 12 });
+        assert_eq!(triangle, Triangle { base: 4, height: 6 });
+        assert_eq!(Square { height: 2 } * 3, Square { height: 6 });
+    }
+
+    #[test]
+    fn wrapper_display() {
+        assert_eq!(Wrapper::<String>(Vec::new()).to_string(), "[]");
+        assert_eq!(Wrapper(vec![String::from("hello")]).to_string(), "[hello]");
+        let words = Wrapper(vec![String::from("hello"), String::from("world")]);
+        assert_eq!(words.to_string(), "[hello, world]");
+        assert_eq!((words.len(), words[1].as_str()), (2, "world"));
+        assert_eq!(Wrapper(vec![1, 2, 3]).to_string(), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn shapes_with_display_get_describe() {
+        assert_eq!(Square { height: 3 }.describe(), "Square(height=3) has area 9");
+        let triangle = Triangle { base: 5, height: 10 };
+        assert_eq!(triangle.describe(), "Triangle(base=5, height=10) has area 25");
+    }
+
+    #[test]
+    fn largest_shape_keeps_the_first_of_a_tie() {
+        let square = Square { height: 2 };
+        let triangle = Triangle { base: 2, height: 4 };
+        let first = largest_shape(&square, &triangle);
+        assert!(std::ptr::addr_eq(first, &square));
+        let first = largest_shape(&triangle, &square);
+        assert!(std::ptr::addr_eq(first, &triangle));
+        let bigger = largest_shape(&square, &Square { height: 3 }).area();
+        assert_eq!(bigger, 9);
+    }
+
+    #[test]
+    fn largest_in_points_into_the_slice() {
+        assert!(largest_in(&[]).is_none());
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Square { height: 2 }),
+            Box::new(Square { height: 5 }),
+            Box::new(Triangle { base: 10, height: 5 }),
+        ];
+        let largest = largest_in(&shapes).unwrap();
+        assert!(std::ptr::addr_eq(largest, shapes[1].as_ref()));
+        assert_eq!(largest.area(), 25);
+    }
+
+    #[test]
+    fn pair_arithmetic() {
+        let pair = Pair::new(3, 4);
+        assert_eq!(pair.sum(), 7);
+        assert_eq!(pair.add(&Pair::new(10, 20)).into_tuple(), (13, 24));
+        assert_eq!(Pair::new(2.5, 1.25).sum(), 3.75);
+    }
+
+    #[test]
+    fn sorted_puts_the_smaller_value_in_x() {
+        assert_eq!(Pair::new(9, 2).sorted().into_tuple(), (2, 9));
+        assert_eq!(Pair::new(2, 9).sorted().into_tuple(), (2, 9));
+        assert_eq!(Pair::new(2.5, 1.25).sorted().into_tuple(), (1.25, 2.5));
+        let nan = Pair::new(f64::NAN, 1.0).sorted();
+        assert!(nan.x.is_nan() && nan.y == 1.0);
+    }
+
+    #[test]
+    fn anything_with_display_can_log() {
+        assert_eq!(Square { height: 2 }.log_line(), "[log] Square(height=2)");
+        assert_eq!(42.log_line(), "[log] 42");
+        assert_eq!(String::from("hi").log_line(), "[log] hi");
+        assert_eq!("".log_line(), "[log] ");
+    }
+
+    #[test]
+    fn squares_and_rectangles_convert_both_ways() {
+        let rectangle = Rectangle::from(Square { height: 4 });
+        assert_eq!((rectangle.height, rectangle.width), (4, 4));
+        let square: Result<Square, _> = rectangle.try_into();
+        assert_eq!(square, Ok(Square { height: 4 }));
+        assert_eq!(Square::try_from(Rectangle { height: 0, width: 0 }), Ok(Square { height: 0 }));
+    }
+
+    #[test]
+    fn an_unequal_rectangle_is_not_a_square() {
+        let err = Square::try_from(Rectangle { height: 2, width: 3 }).unwrap_err();
+        assert_eq!(err, ShapeError::NotASquare(2, 3));
+        assert_eq!(err.to_string(), "a 2 by 3 rectangle isn't a square");
+        assert!(Square::try_from(Rectangle { height: 0, width: 1 }).is_err());
+    }
+
+    #[test]
+    fn fully_qualified_syntax_picks_the_method() {
+        let square = Square { height: 1 };
+        assert_eq!(square.fly(), "*a square flapping its sides*");
+        assert_eq!(Pilot::fly(&square), "This is your captain speaking.");
+        assert_eq!(<Square as Wizard>::fly(&square), "Up!");
+        assert_eq!(Dog::baby_name(), "Spot");
+        assert_eq!(<Dog as Animal>::baby_name(), "puppy");
+    }
+
+    #[test]
+    fn both_halves_can_be_changed_at_once() {
+        let mut pair = Pair::new(1, 2);
+        let (x, y) = pair.both_mut();
+        std::mem::swap(x, y);
+        *x += 10;
+        *y *= 5;
+        assert_eq!(pair.into_tuple(), (12, 5));
+    }
+
+    #[test]
+    fn both_mut_works_for_types_that_are_not_copy() {
+        let mut pair = Pair::new(String::from("left"), String::from("right"));
+        let (x, y) = pair.both_mut();
+        x.push('!');
+        y.clear();
+        pair.x_mut().push('?');
+        pair.y_mut().push_str("new");
+        assert_eq!(pair.into_tuple(), (String::from("left!?"), String::from("new")));
+    }
+
+    #[test]
+    fn squares_sort_by_height() {
+        let mut squares = [Square { height: 5 }, Square { height: -1 }, Square { height: 3 }];
+        squares.sort();
+        let heights: Vec<isize> = squares.iter().map(|square| square.height).collect();
+        assert_eq!(heights, [-1, 3, 5]);
+        assert!(Square { height: 2 } < Square { height: 3 });
+        assert_eq!(squares.iter().max(), Some(&Square { height: 5 }));
+    }
+
+    #[test]
+    fn static_and_dynamic_descriptions_match() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Square { height: 3 }),
+            Box::new(Triangle { base: 3, height: 3 }),
+            Box::new(Circle { radius: 2.0 }),
+        ];
+        for shape in &shapes {
+            assert_eq!(describe_shape(shape.as_ref()), describe_dyn(shape.as_ref()));
+        }
+        assert_eq!(describe_shape(&Square { height: 3 }), "Area is 9");
+        assert_eq!(describe_dyn(&Triangle { base: 3, height: 3 }), "Area is 4");
+        assert_eq!(describe_shape(&Circle { radius: 2.0 }), "Area is 12.566370614359172");
+    }
+
+    #[test]
+    fn compare_and_report_says_which_is_bigger() {
+        let (square, triangle) = (Square { height: 4 }, Triangle { base: 4, height: 4 });
+        assert_eq!(
+            compare_and_report(&square, &triangle),
+            "Square(height=4) is larger than Triangle(base=4, height=4) by 8"
+        );
+        assert_eq!(
+            compare_and_report(&triangle, &square),
+            "Square(height=4) is larger than Triangle(base=4, height=4) by 8"
+        );
+        assert_eq!(
+            compare_and_report(&Triangle { base: 4, height: 8 }, &square),
+            "Triangle(base=4, height=8) and Square(height=4) have the same area"
+        );
+    }
+
+    #[test]
+    fn sum_areas_takes_anything_iterable() {
+        assert_eq!(sum_areas([Square { height: 1 }, Square { height: 2 }]), 5);
+        assert_eq!(sum_areas(vec![Triangle { base: 2, height: 3 }]), 3);
+        assert_eq!(sum_areas(Vec::<Square>::new()), 0);
+        assert_eq!(sum_areas((1..=3).map(|height| Square { height })), 14);
+        let squares = [Square { height: 3 }];
+        assert_eq!(sum_areas(squares.iter().cloned()), 9);
+    }
+
+    #[test]
+    fn either_holds_one_shape_or_the_other() {
+        let left = return_area_either(true);
+        let right = return_area_either(false);
+        assert!(matches!(left, Either::Left(Square { height: 15 })));
+        assert!(matches!(right, Either::Right(Triangle { base: 1, height: 4 })));
+        assert_eq!((left.area(), right.area()), (225, 2));
+        assert_eq!(describe_shape(left.as_shape()), "Area is 225");
+        assert_eq!(describe_dyn(right.as_shape()), "Area is 2");
+    }
+
+    #[test]
+    fn either_maps_only_its_own_side() {
+        let left: Either<Square, Triangle> = Either::Left(Square { height: 2 });
+        let scaled = left.map_left(|square| square * 3).map_right(|triangle| triangle * 100);
+        assert!(matches!(scaled, Either::Left(Square { height: 6 })));
+        let right: Either<Square, Triangle> = Either::Right(Triangle { base: 2, height: 2 });
+        let base = right.map_left(|_| -> isize { unreachable!() }).map_right(|t| t.base);
+        assert!(matches!(base, Either::Right(2)));
+    }
+
+    #[test]
+    fn pairs_borrow_their_neighbours() {
+        let items = [1, 2, 3];
+        let found: Vec<(i32, i32)> = pairs(&items).map(|pair| (*pair.x, *pair.y)).collect();
+        assert_eq!(found, [(1, 2), (2, 3)]);
+        let first = pairs(&items).next().unwrap();
+        assert!(std::ptr::eq(first.x, &items[0]) && std::ptr::eq(first.y, &items[1]));
+        assert_eq!(pairs::<i32>(&[]).count(), 0);
+        assert_eq!(pairs(&[1]).count(), 0);
+    }
+
+    #[test]
+    fn pair_up_leaves_out_an_odd_one() {
+        let tuples = |items: &[i32]| -> Vec<(i32, i32)> {
+            pair_up(items).into_iter().map(Pair::into_tuple).collect()
+        };
+        assert_eq!(tuples(&[1, 2, 3, 4]), [(1, 2), (3, 4)]);
+        assert_eq!(tuples(&[1, 2, 3]), [(1, 2)]);
+        assert!(tuples(&[1]).is_empty());
+        assert!(tuples(&[]).is_empty());
+    }
+
+    #[test]
+    fn larger_compares_different_shapes() {
+        let (square, triangle) = (Square { height: 4 }, Triangle { base: 4, height: 8 });
+        assert_eq!(larger(&square, &triangle), 16);
+        assert_eq!(larger(&Square { height: 5 }, &triangle), 25);
+        assert_eq!(larger(&square, &Triangle { base: 10, height: 10 }), 50);
+        let boxed: Box<dyn Shape> = Box::new(Circle { radius: 1.0 });
+        assert_eq!(boxed.measure(), 3);
+    }
+
+    #[test]
+    fn rank_is_largest_first_and_stable() {
+        let mut squares = [Square { height: 2 }, Square { height: 7 }, Square { height: 4 }];
+        rank(&mut squares);
+        assert_eq!(squares.map(|square| square.height), [7, 4, 2]);
+        let mut triangles = [
+            Triangle { base: 2, height: 4 },
+            Triangle { base: 6, height: 6 },
+            Triangle { base: 4, height: 2 },
+        ];
+        rank(&mut triangles);
+        assert_eq!(triangles.map(|triangle| triangle.base), [6, 2, 4]);
+        let mut empty: [Square; 0] = [];
+        rank(&mut empty);
+    }
+
+    #[test]
+    fn downcasting_finds_only_squares() {
+        let square: Box<dyn Shape> = Box::new(Square { height: 7 });
+        let triangle: Box<dyn Shape> = Box::new(Triangle { base: 7, height: 7 });
+        assert_eq!(downcast_square(square.as_ref()), Some(&Square { height: 7 }));
+        assert_eq!(downcast_square(triangle.as_ref()), None);
+        assert_eq!(downcast_square(&Circle { radius: 7.0 }), None);
+        let copy = square.clone();
+        assert_eq!(downcast_square(copy.as_ref()), Some(&Square { height: 7 }));
+    }
+
+    fn sides_of<T: ShapeInfo>() -> usize {
+        T::SIDES
+    }
+
+    #[test]
+    fn shape_info_constants() {
+        assert_eq!((Square::SIDES, Triangle::SIDES, Circle::SIDES), (4, 3, 0));
+        assert_eq!((sides_of::<Square>(), sides_of::<Triangle>()), (4, 3));
+        assert_eq!(Circle::NAME, "circle");
+        assert_eq!(total_sides::<Triangle>(5), 15);
+        assert_eq!(total_sides::<Circle>(5), 0);
+    }
+
+    #[test]
+    fn info_lines() {
+        assert_eq!(info_line(&Square { height: 3 }), "square with 4 sides, area 9");
+        let triangle = Triangle { base: 4, height: 5 };
+        assert_eq!(info_line(&triangle), "triangle with 3 sides, area 10");
+        assert_eq!(info_line(&Circle { radius: 1.0 }), "circle with 0 sides, area 3");
+    }
+
+    #[test]
+    fn pair_is_a_container_of_two() {
+        let pair = Pair::new('a', 'b');
+        assert_eq!((pair.get(0), pair.get(1), pair.get(2)), (Some(&'a'), Some(&'b'), None));
+        assert_eq!(describe_container(&pair), "2 items, the first is 'a'");
+        assert!(!Container::is_empty(&pair));
+    }
+
+    #[test]
+    fn stack_container_counts_from_the_top() {
+        let mut stack = Stack::new();
+        assert_eq!(describe_container(&stack), "0 items");
+        assert_eq!(Container::first(&stack), None);
+        stack.push(1);
+        stack.push(2);
+        assert_eq!(describe_container(&stack), "2 items, the first is 2");
+        assert_eq!(Container::get(&stack, 5), None);
+    }
+
+    #[test]
+    fn shape_collection_container() {
+        let mut collection = ShapeCollection::new();
+        assert_eq!(describe_container(&collection), "0 items");
+        collection.push(Box::new(Square { height: 2 }));
+        collection.push(Box::new(Circle { radius: 1.0 }));
+        assert_eq!(describe_container(&collection), "2 items, the first is Shape { area: 4 }");
+        assert_eq!(Container::get(&collection, 1).map(|shape| shape.area()), Some(3));
+        assert!(Container::get(&collection, 2).is_none());
+    }
+
+    fn areas_of(shapes: &[&dyn Shape]) -> Vec<isize> {
+        shapes.iter().map(|shape| shape.area()).collect()
+    }
+
+    fn mixed() -> Vec<Box<dyn Shape>> {
+        vec![
+            Box::new(Square { height: 5 }),
+            Box::new(Triangle { base: 2, height: 6 }),
+            Box::new(Square { height: 3 }),
+            Box::new(Triangle { base: 10, height: 5 }),
+        ]
+    }
+
+    #[test]
+    fn partition_puts_the_threshold_itself_above() {
+        let shapes = mixed();
+        let (below, rest) = shapes.partition_by_area(9);
+        assert_eq!((areas_of(&below), areas_of(&rest)), (vec![6], vec![25, 9, 25]));
+        let (below, rest) = shapes.partition_by_area(0);
+        assert_eq!((below.len(), rest.len()), (0, 4));
+        let (below, rest) = shapes.partition_by_area(26);
+        assert_eq!((areas_of(&below), rest.len()), (vec![25, 6, 9, 25], 0));
+    }
+
+    #[test]
+    fn slice_helpers_on_an_empty_slice() {
+        let none: Vec<Box<dyn Shape>> = Vec::new();
+        assert!(none.largest().is_none());
+        let (below, rest) = none.partition_by_area(10);
+        assert!(below.is_empty() && rest.is_empty());
+    }
+
+    #[test]
+    fn slice_largest_keeps_the_first_of_a_tie() {
+        let shapes = mixed();
+        let largest = shapes.largest().unwrap();
+        assert!(std::ptr::addr_eq(largest, shapes[0].as_ref()));
+    }
+}
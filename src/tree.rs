@@ -0,0 +1,184 @@
+use crate::output::Output;
+use crate::traits::{Circle, Shape, Square, Triangle};
+
+//A tree of shapes. Unlike CompositeShape the nesting is part of the type, so it is possible to
+// ask how deep the tree goes or how many shapes are at the bottom of it. The Vec inside Node is
+// what makes the recursion work, the enum would have an infinite size without some indirection.
+pub enum ShapeTree {
+    Leaf(Box<dyn Shape>),
+    Node(Vec<ShapeTree>),
+}
+
+impl ShapeTree {
+    //An empty node adds nothing to the area.
+    pub fn total_area(&self) -> isize {
+        match self {
+            ShapeTree::Leaf(shape) => shape.area(),
+            ShapeTree::Node(children) => children.iter().map(|child| child.total_area()).sum(),
+        }
+    }
+
+    //A leaf and an empty node both have a depth of 1, every level of nesting above them adds 1.
+    pub fn depth(&self) -> usize {
+        match self {
+            ShapeTree::Leaf(_) => 1,
+            ShapeTree::Node(children) => {
+                1 + children.iter().map(|child| child.depth()).max().unwrap_or(0)
+            }
+        }
+    }
+
+    pub fn count_leaves(&self) -> usize {
+        match self {
+            ShapeTree::Leaf(_) => 1,
+            ShapeTree::Node(children) => children.iter().map(|child| child.count_leaves()).sum(),
+        }
+    }
+}
+
+//The same tree, but it can hold anything. Instead of writing a method for every aggregate like
+// ShapeTree does, fold lets the caller decide what to build up.
+pub enum Tree<T> {
+    Leaf(T),
+    Node(Vec<Tree<T>>),
+}
+
+impl<T> Tree<T> {
+    //Leaves are visited left to right, children before the nodes that come after them.
+    pub fn fold<A>(&self, init: A, f: impl Fn(A, &T) -> A) -> A {
+        self.fold_ref(init, &f)
+    }
+
+    //Recursing through fold itself would mean calling it with &F, then &&F and so on, which
+    // the compiler can't generate an end to. Passing the same &F down keeps it to one type.
+    fn fold_ref<A, F: Fn(A, &T) -> A>(&self, init: A, f: &F) -> A {
+        match self {
+            Tree::Leaf(value) => f(init, value),
+            Tree::Node(children) => children.iter().fold(init, |acc, child| child.fold_ref(acc, f)),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        match self {
+            Tree::Leaf(_) => 1,
+            Tree::Node(children) => {
+                1 + children.iter().map(|child| child.depth()).max().unwrap_or(0)
+            }
+        }
+    }
+
+    pub fn count_leaves(&self) -> usize {
+        self.fold(0, |count, _| count + 1)
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let shapes = ShapeTree::Node(Vec::from([
+        ShapeTree::Node(Vec::from([
+            ShapeTree::Leaf(Box::new(Square { height: 10 })),
+            ShapeTree::Leaf(Box::new(Triangle { base: 5, height: 10 })),
+        ])),
+        ShapeTree::Leaf(Box::new(Circle { radius: 1.0 })),
+        ShapeTree::Node(Vec::new()),
+    ]));
+    out.line(format!(
+        "shape tree area: {} depth: {} leaves: {}",
+        shapes.total_area(),
+        shapes.depth(),
+        shapes.count_leaves()
+    ));
+
+    let empty = ShapeTree::Node(Vec::new());
+    let single = ShapeTree::Leaf(Box::new(Square { height: 3 }));
+    out.line(format!(
+        "empty node area: {} depth: {} leaves: {}",
+        empty.total_area(),
+        empty.depth(),
+        empty.count_leaves()
+    ));
+    out.line(format!(
+        "single leaf area: {} depth: {} leaves: {}",
+        single.total_area(),
+        single.depth(),
+        single.count_leaves()
+    ));
+
+    //Wrapping a leaf in a node 11 times.
+    let mut deep = Tree::Leaf(Square { height: 2 });
+    for _ in 0..11 {
+        deep = Tree::Node(Vec::from([deep]));
+    }
+    out.line(format!("deep tree depth: {} leaves: {}", deep.depth(), deep.count_leaves()));
+
+    let squares = Tree::Node(Vec::from([
+        Tree::Leaf(Square { height: 1 }),
+        Tree::Node(Vec::from([Tree::Leaf(Square { height: 2 }), Tree::Leaf(Square { height: 3 })])),
+        Tree::Node(Vec::new()),
+    ]));
+    let areas = squares.fold(Vec::new(), |mut areas, square| {
+        areas.push(square.area());
+        areas
+    });
+    out.line(format!("areas folded into a Vec: {:?}", areas));
+    let total = squares.fold(0, |total, square| total + square.area());
+    out.line(format!("total folded area: {}", total));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(height: isize) -> ShapeTree {
+        ShapeTree::Leaf(Box::new(Square { height }))
+    }
+
+    fn squares() -> Tree<Square> {
+        let leaf = |height| Tree::Leaf(Square { height });
+        Tree::Node(Vec::from([
+            leaf(1),
+            Tree::Node(Vec::from([leaf(2), leaf(3)])),
+            Tree::Node(Vec::new()),
+        ]))
+    }
+
+    #[test]
+    fn shape_tree_adds_up_every_level() {
+        let tree = ShapeTree::Node(Vec::from([
+            ShapeTree::Node(Vec::from([leaf(10), leaf(1)])),
+            leaf(2),
+            ShapeTree::Node(Vec::new()),
+        ]));
+        assert_eq!(tree.total_area(), 105);
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(tree.count_leaves(), 3);
+    }
+
+    #[test]
+    fn empty_node_and_single_leaf() {
+        let empty = ShapeTree::Node(Vec::new());
+        assert_eq!((empty.total_area(), empty.depth(), empty.count_leaves()), (0, 1, 0));
+        let single = leaf(3);
+        assert_eq!((single.total_area(), single.depth(), single.count_leaves()), (9, 1, 1));
+    }
+
+    #[test]
+    fn fold_visits_leaves_left_to_right() {
+        let areas = squares().fold(Vec::new(), |mut areas, square| {
+            areas.push(square.area());
+            areas
+        });
+        assert_eq!(areas, [1, 4, 9]);
+        assert_eq!(squares().fold(0, |total, square| total + square.area()), 14);
+    }
+
+    #[test]
+    fn generic_tree_depth_and_leaves() {
+        assert_eq!((squares().depth(), squares().count_leaves()), (3, 3));
+        let mut deep = Tree::Leaf(1);
+        for _ in 0..11 {
+            deep = Tree::Node(Vec::from([deep]));
+        }
+        assert_eq!((deep.depth(), deep.count_leaves()), (12, 1));
+        assert_eq!(Tree::<i32>::Node(Vec::new()).fold(7, |acc, _| acc + 1), 7);
+    }
+}
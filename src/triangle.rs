@@ -0,0 +1,184 @@
+use crate::output::Output;
+use crate::traits::{Shape, ShapeError, ShapeInfo};
+use std::any::Any;
+
+//Float comparisons are done with a small relative tolerance so that something like a 3-4-5
+// triangle scaled by 0.1 still counts as a right triangle.
+const TOLERANCE: f64 = 1e-9;
+
+fn nearly_equal(a: f64, b: f64) -> bool {
+    (a - b).abs() <= TOLERANCE * a.abs().max(b.abs())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TriangleKind {
+    Equilateral,
+    Isosceles,
+    Scalene,
+}
+
+//The Triangle in the traits module is always a right triangle made from a base and a height.
+// This one is any triangle at all, described by the lengths of its three sides.
+#[derive(Debug, Clone)]
+pub struct SidedTriangle {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl SidedTriangle {
+    //The sides have to satisfy the triangle inequality, each one shorter than the other two put
+    // together. A degenerate triangle like 1, 1, 2 is flat and gets rejected.
+    pub fn new(a: f64, b: f64, c: f64) -> Result<SidedTriangle, ShapeError> {
+        for side in [a, b, c] {
+            if side.is_nan() || side <= 0.0 {
+                return Err(ShapeError::NonPositiveSide(side));
+            }
+        }
+        if a + b <= c || a + c <= b || b + c <= a {
+            return Err(ShapeError::NotATriangle(a, b, c));
+        }
+        Ok(SidedTriangle { a, b, c })
+    }
+
+    pub fn sides(&self) -> (f64, f64, f64) {
+        (self.a, self.b, self.c)
+    }
+
+    pub fn classify(&self) -> TriangleKind {
+        let ab = nearly_equal(self.a, self.b);
+        let bc = nearly_equal(self.b, self.c);
+        let ac = nearly_equal(self.a, self.c);
+        if ab && bc {
+            TriangleKind::Equilateral
+        } else if ab || bc || ac {
+            TriangleKind::Isosceles
+        } else {
+            TriangleKind::Scalene
+        }
+    }
+
+    //Pythagoras, the two shorter sides squared add up to the longest side squared.
+    pub fn is_right(&self) -> bool {
+        let mut sides = [self.a, self.b, self.c];
+        sides.sort_by(f64::total_cmp);
+        nearly_equal(sides[0] * sides[0] + sides[1] * sides[1], sides[2] * sides[2])
+    }
+}
+
+impl ShapeInfo for SidedTriangle {
+    const SIDES: usize = 3;
+    const NAME: &'static str = "triangle";
+}
+
+impl Shape for SidedTriangle {
+    fn area(&self) -> isize {
+        self.area_f64().round() as isize
+    }
+
+    //Heron's formula. For a nearly flat triangle the product rounds down towards zero, and for
+    // sides that never went through new it can go below zero. It is clamped before the square
+    // root so that comes out as an area of 0 instead of NaN.
+    fn area_f64(&self) -> f64 {
+        let s = self.perimeter() / 2.0;
+        let product = s * (s - self.a) * (s - self.b) * (s - self.c);
+        product.max(0.0).sqrt()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.a + self.b + self.c
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    for (a, b, c) in [(3.0, 4.0, 5.0), (2.0, 2.0, 2.0), (5.0, 5.0, 8.0), (1.0, 1.0, 2.0)] {
+        match SidedTriangle::new(a, b, c) {
+            Ok(triangle) => out.line(format!(
+                "{a}, {b}, {c} is {:?}, right? {}, area {}",
+                triangle.classify(),
+                triangle.is_right(),
+                triangle.area_f64()
+            )),
+            Err(e) => out.line(format!("could not make a triangle: {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sides_must_be_positive_numbers() {
+        assert!(matches!(SidedTriangle::new(0.0, 1.0, 1.0), Err(ShapeError::NonPositiveSide(_))));
+        assert!(matches!(SidedTriangle::new(1.0, -2.0, 2.0), Err(ShapeError::NonPositiveSide(_))));
+        assert!(SidedTriangle::new(f64::NAN, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn flat_or_impossible_triangles_are_rejected() {
+        assert!(matches!(SidedTriangle::new(1.0, 1.0, 2.0), Err(ShapeError::NotATriangle(..))));
+        assert!(matches!(SidedTriangle::new(1.0, 10.0, 2.0), Err(ShapeError::NotATriangle(..))));
+        assert_eq!(SidedTriangle::new(2.0, 3.0, 4.0).unwrap().sides(), (2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn classify_by_equal_sides() {
+        let kind = |a, b, c| SidedTriangle::new(a, b, c).unwrap().classify();
+        assert_eq!(kind(2.0, 2.0, 2.0), TriangleKind::Equilateral);
+        assert_eq!(kind(5.0, 8.0, 5.0), TriangleKind::Isosceles);
+        assert_eq!(kind(3.0, 4.0, 5.0), TriangleKind::Scalene);
+        //0.1 + 0.2 isn't exactly 0.3, but it is close enough to count as equal.
+        assert_eq!(kind(0.1 + 0.2, 0.3, 0.5), TriangleKind::Isosceles);
+    }
+
+    #[test]
+    fn right_triangles_in_any_order_and_scale() {
+        let right = |a, b, c| SidedTriangle::new(a, b, c).unwrap().is_right();
+        assert!(right(3.0, 4.0, 5.0));
+        assert!(right(5.0, 3.0, 4.0));
+        assert!(right(0.3, 0.4, 0.5));
+        assert!(!right(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn herons_formula() {
+        let area = |a, b, c| SidedTriangle::new(a, b, c).unwrap().area_f64();
+        assert_eq!(area(3.0, 4.0, 5.0), 6.0);
+        assert!((area(2.0, 2.0, 2.0) - 3f64.sqrt()).abs() < 1e-12);
+        assert_eq!(SidedTriangle::new(5.0, 5.0, 8.0).unwrap().area(), 12);
+        assert_eq!(<SidedTriangle as ShapeInfo>::SIDES, 3);
+    }
+
+    #[test]
+    fn nearly_flat_triangles_are_kept() {
+        let c = 1.999_999_999;
+        let triangle = SidedTriangle::new(1.0, 1.0, c).unwrap();
+        assert_eq!(triangle.classify(), TriangleKind::Isosceles);
+        assert!(!triangle.is_right());
+        //With two sides of 1 the height over c is sqrt(1 - (c / 2)^2).
+        let expected = c / 2.0 * (1.0 - c * c / 4.0).sqrt();
+        let area = triangle.area_f64();
+        assert!(area > 0.0 && (area - expected).abs() < 1e-9 * expected, "{area}");
+        assert_eq!(triangle.area(), 0);
+        let flattest = SidedTriangle::new(1.0, 1.0, 2.0 - f64::EPSILON).unwrap();
+        assert!(flattest.area_f64() >= 0.0 && flattest.area_f64() < 1e-7);
+    }
+
+    #[test]
+    fn a_product_below_zero_is_clamped() {
+        //Too long a side to get past new, but it shows what the clamp is there for.
+        let impossible = SidedTriangle { a: 1.0, b: 1.0, c: 2.000_000_001 };
+        let s = impossible.perimeter() / 2.0;
+        assert!(s * (s - 1.0) * (s - 1.0) * (s - impossible.c) < 0.0);
+        assert_eq!(impossible.area_f64(), 0.0);
+    }
+}
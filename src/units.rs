@@ -0,0 +1,157 @@
+use crate::output::Output;
+use std::fmt::Display;
+use std::ops::{Add, Mul};
+
+//Newtypes around f64 so lengths in different units can't be mixed up. Meters + Feet doesn't
+// compile, one of them has to be converted first.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Meters(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Feet(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SquareMeters(pub f64);
+
+pub const METERS_PER_FOOT: f64 = 0.3048;
+
+impl From<Feet> for Meters {
+    fn from(feet: Feet) -> Self {
+        Meters(feet.0 * METERS_PER_FOOT)
+    }
+}
+
+impl From<Meters> for Feet {
+    fn from(meters: Meters) -> Self {
+        Feet(meters.0 / METERS_PER_FOOT)
+    }
+}
+
+//Only the same unit can be added together. Scaling by a plain number keeps the unit.
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+impl Add for Feet {
+    type Output = Feet;
+
+    fn add(self, other: Feet) -> Feet {
+        Feet(self.0 + other.0)
+    }
+}
+
+impl Mul<f64> for Meters {
+    type Output = Meters;
+
+    fn mul(self, factor: f64) -> Meters {
+        Meters(self.0 * factor)
+    }
+}
+
+impl Mul<f64> for Feet {
+    type Output = Feet;
+
+    fn mul(self, factor: f64) -> Feet {
+        Feet(self.0 * factor)
+    }
+}
+
+//Meters times Meters gives an area, not more Meters.
+impl Mul for Meters {
+    type Output = SquareMeters;
+
+    fn mul(self, other: Meters) -> SquareMeters {
+        SquareMeters(self.0 * other.0)
+    }
+}
+
+//The number is formatted with whatever options were given, so `{:.2}` rounds before the unit is
+// added on.
+impl Display for Meters {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)?;
+        write!(f, " m")
+    }
+}
+
+impl Display for Feet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)?;
+        write!(f, " ft")
+    }
+}
+
+impl Display for SquareMeters {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)?;
+        write!(f, " m²")
+    }
+}
+
+pub struct MeasuredSquare {
+    pub side: Meters,
+}
+
+impl MeasuredSquare {
+    pub fn area(&self) -> SquareMeters {
+        self.side * self.side
+    }
+}
+
+pub fn run_demo(out: &mut dyn Output) {
+    let side = Feet(10.0);
+    let square = MeasuredSquare { side: Meters::from(side) };
+    out.line(format!(
+        "a {side} square has sides of {:.3} and an area of {:.3}",
+        square.side,
+        square.area()
+    ));
+    let two_meters = MeasuredSquare { side: Meters(2.0) };
+    out.line(format!("a 2 m square has an area of {}", two_meters.area()));
+
+    let total = Meters(1.5) + Meters::from(Feet(3.0)) * 2.0;
+    out.line(format!("1.5 m plus twice 3 ft is {total:.4} or {:.2}", Feet::from(total)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn converting_there_and_back() {
+        assert!(close(Meters::from(Feet(10.0)).0, 3.048));
+        assert!(close(Feet::from(Meters(0.3048)).0, 1.0));
+        assert!(close(Feet::from(Meters::from(Feet(12.5))).0, 12.5));
+    }
+
+    #[test]
+    fn adding_and_scaling_keep_the_unit() {
+        assert_eq!(Meters(1.5) + Meters(2.0), Meters(3.5));
+        assert_eq!(Feet(1.0) + Feet(2.0), Feet(3.0));
+        assert_eq!(Meters(1.5) * 2.0, Meters(3.0));
+        assert_eq!(Feet(3.0) * 0.5, Feet(1.5));
+        assert!(Meters(1.0) < Meters(2.0));
+    }
+
+    #[test]
+    fn meters_times_meters_is_an_area() {
+        assert_eq!(Meters(2.0) * Meters(3.0), SquareMeters(6.0));
+        assert_eq!(MeasuredSquare { side: Meters(2.0) }.area(), SquareMeters(4.0));
+    }
+
+    #[test]
+    fn display_passes_the_precision_on() {
+        assert_eq!(Meters(2.0).to_string(), "2 m");
+        assert_eq!(format!("{:.2}", Meters(1.23456)), "1.23 m");
+        assert_eq!(format!("{:.1}", Feet(10.0)), "10.0 ft");
+        assert_eq!(format!("{:.3}", SquareMeters(9.290304)), "9.290 m²");
+    }
+}
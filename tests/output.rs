@@ -0,0 +1,42 @@
+mod support;
+
+use rust_book_chapter_10::{generics, lifetimes, traits, RunOptions};
+use support::assert_matches_fixture;
+
+//Each section is run against a Vec<String> and compared with what it printed last time. If a
+// change to the output is on purpose, regenerate the fixtures with
+// UPDATE_FIXTURES=1 cargo test --test output
+
+#[test]
+fn generics_output() {
+    let mut out: Vec<String> = Vec::new();
+    generics::run_demo(&mut out);
+    assert_matches_fixture("generics", &out);
+}
+
+#[test]
+fn traits_output() {
+    let mut out: Vec<String> = Vec::new();
+    traits::run_demo(&mut out);
+    assert_matches_fixture("traits", &out);
+}
+
+#[test]
+fn lifetimes_output() {
+    let mut out: Vec<String> = Vec::new();
+    lifetimes::run_demo(&mut out);
+    assert_matches_fixture("lifetimes", &out);
+}
+
+//Not a fixture, but it goes through the same entry point. Asking for a section that doesn't
+// exist should come back as an error before anything is printed.
+#[test]
+fn unknown_section_is_an_error() {
+    let mut out: Vec<String> = Vec::new();
+    let result = rust_book_chapter_10::run(Some("pentagons"), &mut out, RunOptions::default());
+    match result {
+        Err(e) => assert!(e.to_string().starts_with("unknown section 'pentagons'")),
+        Ok(_) => panic!("an unknown section ran"),
+    }
+    assert!(out.is_empty());
+}
@@ -0,0 +1,74 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+//Lines that match are printed with two spaces in front, lines only in the expected output get a
+// `-` and lines only in the actual output get a `+`. It's a simple longest common subsequence
+// diff, which is plenty for fixtures with a few hundred lines.
+pub fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    //lengths[i][j] is the length of the longest common subsequence of expected[i..] and
+    // actual[j..].
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected[i] == actual[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            diff.push_str(&format!("  {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < actual.len()
+            && (i == expected.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+        {
+            diff.push_str(&format!("+ {}\n", actual[j]));
+            j += 1;
+        } else {
+            diff.push_str(&format!("- {}\n", expected[i]));
+            i += 1;
+        }
+    }
+    diff
+}
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{name}.txt"))
+}
+
+//Compares the captured lines against tests/fixtures/<name>.txt. Running the tests with
+// UPDATE_FIXTURES=1 writes the captured output over the fixture instead.
+pub fn assert_matches_fixture(name: &str, lines: &[String]) {
+    let mut actual = lines.join("\n");
+    actual.push('\n');
+    let path = fixture_path(name);
+
+    if env::var("UPDATE_FIXTURES").is_ok_and(|value| value == "1") {
+        fs::write(&path, &actual).expect("Could not write the fixture");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("Missing fixture {}, run with UPDATE_FIXTURES=1 to create it", path.display())
+    });
+    if expected != actual {
+        panic!(
+            "output for {name} doesn't match {}\n{}",
+            path.display(),
+            line_diff(&expected, &actual)
+        );
+    }
+}